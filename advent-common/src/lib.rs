@@ -0,0 +1,144 @@
+/*!
+ * Helpers shared between the days, mostly for reading puzzle input.
+ */
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Read, BufRead, BufReader};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+/**
+ * A day's puzzle. The input is parsed once, and then both parts are solved from it.
+ */
+pub trait Puzzle {
+    /// the parsed puzzle input
+    type Input;
+
+    /// Parse the puzzle input from a reader
+    fn parse(input: impl Read) -> Result<Self::Input, Box<dyn Error>>;
+    /// Solve part 1, returning the answer as text
+    fn part1(input: &Self::Input) -> String;
+    /// Solve part 2, returning the answer as text
+    fn part2(input: &Self::Input) -> String;
+}
+
+/**
+ * Opens a puzzle input file. Files ending in `.gz` are decompressed as they're read.
+ */
+pub fn open_input(path: &str) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    let file = File::open(path)?;
+    match Path::new(path).extension() {
+        Some(ext) if ext == "gz" => Ok(Box::new(GzDecoder::new(file))),
+        _ => Ok(Box::new(file)),
+    }
+}
+
+/**
+ * Reads all lines from a reader
+ */
+pub fn read_lines(input: impl Read) -> io::Result<Vec<String>> {
+    BufReader::new(input)
+        .lines()
+        .collect()
+}
+
+/**
+ * Reads lines from a reader, grouping them into batches. Batches are separated by empty lines.
+ *
+ * ```
+ * use std::io::Cursor;
+ * assert_eq!(advent_common::read_batches(Cursor::new("a\nb\n\nc")).unwrap(),
+ *            vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string()]]);
+ * ```
+ */
+pub fn read_batches(input: impl Read) -> io::Result<Vec<Vec<String>>> {
+    read_batches_with(input, str::is_empty)
+}
+
+/**
+ * Like `read_batches`, but batches are separated by any line that `is_separator` picks out. Separator lines aren't
+ * included in any batch.
+ */
+pub fn read_batches_with(input: impl Read, is_separator: impl Fn(&str) -> bool) -> io::Result<Vec<Vec<String>>> {
+    let mut batches: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for line in BufReader::new(input).lines() {
+        let line: String = line?;
+        if is_separator(&line) {
+            batches.push(current);
+            current = Vec::new();
+        } else {
+            current.push(line);
+        }
+    }
+    if ! current.is_empty() {
+        batches.push(current);
+    }
+    Ok(batches)
+}
+
+/**
+ * Returns the first value missing from a sorted sequence, or `None` if there are no holes in it.
+ *
+ * ```
+ * assert_eq!(advent_common::first_gap(&[3, 4, 6, 7, 9]), Some(5));
+ * ```
+ */
+pub fn first_gap(sorted: &[u32]) -> Option<u32> {
+    sorted.windows(2)
+        .find(|window| window[0] + 1 < window[1])
+        .map(|window| window[0] + 1)
+}
+
+#[test]
+fn test_read_batches_with() {
+    use std::io::Cursor;
+    assert_eq!(read_batches_with(Cursor::new("a\nb\n---\nc\n"), |line| line == "---").unwrap(),
+               vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string()]]);
+    // blank lines are just lines when they aren't the separator
+    assert_eq!(read_batches_with(Cursor::new("a\n\n\x0c\nc"), |line| line == "\x0c").unwrap(),
+               vec![vec!["a".to_string(), "".to_string()], vec!["c".to_string()]]);
+}
+
+#[test]
+fn test_first_gap() {
+    assert_eq!(first_gap(&[3, 4, 5, 6]), None);
+    assert_eq!(first_gap(&[3, 4, 6, 7]), Some(5));
+    assert_eq!(first_gap(&[3, 3, 4, 8]), Some(5));
+    assert_eq!(first_gap(&[]), None);
+    assert_eq!(first_gap(&[1]), None);
+}
+
+#[test]
+fn test_read_lines() {
+    use std::io::Cursor;
+    assert_eq!(read_lines(Cursor::new("abc\n\ndef\n")).unwrap(), vec!["abc".to_string(), "".to_string(), "def".to_string()]);
+    assert!(read_lines(Cursor::new("")).unwrap().is_empty());
+}
+
+#[test]
+fn test_open_input() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/sample.txt.gz");
+    assert_eq!(read_lines(open_input(path).unwrap()).unwrap(), vec!["abc".to_string(), "".to_string(), "def".to_string()]);
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/sample.txt");
+    assert_eq!(read_lines(open_input(path).unwrap()).unwrap(), vec!["abc".to_string(), "".to_string(), "def".to_string()]);
+
+    assert!(open_input(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/missing.txt")).is_err());
+}
+
+#[test]
+fn test_read_batches() {
+    use std::io::Cursor;
+    let data = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(read_batches(Cursor::new(data)).unwrap(), vec![
+        vec!["abc".to_string()],
+        vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        vec!["ab".to_string(), "ac".to_string()],
+        vec!["a".to_string(), "a".to_string(), "a".to_string(), "a".to_string()],
+        vec!["b".to_string()],
+    ]);
+    // a trailing empty line doesn't make an empty batch
+    assert_eq!(read_batches(Cursor::new("a\nb\n\n")).unwrap(), vec![vec!["a".to_string(), "b".to_string()]]);
+}
@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::io::Read;
+
+use advent_common::{open_input, Puzzle};
+use clap::{App, Arg};
+
+use day_1::ReportRepair;
+use day_5::BinaryBoarding;
+use day_6::CustomCustoms;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let matches = App::new("advent2020")
+        .arg(Arg::with_name("day")
+             .required(true)
+             .takes_value(true)
+             .help("which day's puzzle to solve"))
+        .arg(Arg::with_name("input")
+             .required(true)
+             .takes_value(true)
+             .help("input file for the day"))
+        .get_matches();
+
+    let day: u32 = matches.value_of("day").unwrap().parse()?;
+    let input = open_input(matches.value_of("input").unwrap())?;
+    let (part1, part2) = dispatch(day, input)?;
+    println!("part 1: {}", part1);
+    println!("part 2: {}", part2);
+    Ok(())
+}
+
+/**
+ * Parses the input once and solves both parts of a puzzle
+ */
+pub fn solve<P: Puzzle>(input: impl Read) -> Result<(String, String), Box<dyn Error>> {
+    let parsed = P::parse(input)?;
+    Ok((P::part1(&parsed), P::part2(&parsed)))
+}
+
+/**
+ * Solves both parts of `day`'s puzzle. Errors if that day isn't wired into the runner yet.
+ */
+pub fn dispatch(day: u32, input: impl Read) -> Result<(String, String), Box<dyn Error>> {
+    match day {
+        1 => solve::<ReportRepair>(input),
+        5 => solve::<BinaryBoarding>(input),
+        6 => solve::<CustomCustoms>(input),
+        _ => Err(format!("no solver for day {}", day).into()),
+    }
+}
+
+#[test]
+fn test_dispatch() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(dispatch(6, Cursor::new(answers)).unwrap(), ("11".to_string(), "6".to_string()));
+
+    let report = "1721\n979\n366\n299\n675\n1456";
+    assert_eq!(dispatch(1, Cursor::new(report)).unwrap(), ("514579".to_string(), "241861950".to_string()));
+
+    assert!(dispatch(4, Cursor::new("")).is_err());
+    assert!(dispatch(1, Cursor::new("not a number")).is_err());
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_1::{get_sum_to, get_sum_tree, SumTree};
+
+const TARGET: i64 = 2020;
+
+/// entries in every generated expense report
+const INPUT_LEN: usize = 1000;
+
+/**
+ * Builds an expense report of `len` entries with exactly one pair and exactly one triple that sum up to `TARGET`.
+ *
+ * Filler entries are all bigger than half of `TARGET`, so two of them can never make a match, and any filler value
+ * that would complete a match with the planted entries is skipped. The pair is planted in the back half of the report,
+ * and the triple is spread over its first eighth.
+ */
+fn generate_input(len: usize) -> Vec<i64> {
+    // (entry, where it goes), in order of position
+    let planted = [(200, 2), (400, len / 16), (1420, len / 8), (300, len / 2), (1720, len * 9 / 10)];
+    let mut forbidden: Vec<i64> = planted.iter().map(|(a, _)| TARGET - a).collect();
+    for (idx, (a, _)) in planted.iter().enumerate() {
+        forbidden.extend(planted[idx + 1..].iter().map(|(b, _)| TARGET - a - b));
+    }
+
+    // some not-very-random numbers
+    let mut seed: i64 = 12345;
+    let mut input = Vec::with_capacity(len);
+    while input.len() < len - planted.len() {
+        seed = (seed * 1103515245 + 12345) % 2147483648;
+        let value = TARGET / 2 + 1 + seed % (TARGET / 2 - 1);
+        if !forbidden.contains(&value) {
+            input.push(value);
+        }
+    }
+    for (value, position) in planted.iter() {
+        input.insert(*position, *value);
+    }
+    input
+}
+
+/**
+ * The tree strategy, but it stops inserting as soon as the tree holds a match instead of building it from the whole
+ * report first
+ */
+fn sum_tree_until_match(to_sum: i64, input: &[i64]) -> Option<Vec<i64>> {
+    let mut tree = SumTree::new(to_sum);
+    input.iter().find_map(|value| tree.insert_and_check(*value, TARGET))
+}
+
+/**
+ * Every strategy should find the same entries, though not necessarily in the same order
+ */
+fn check_same_answer(to_sum: i64, input: &[i64]) {
+    let mut permuted: Vec<i64> = get_sum_to(TARGET, to_sum, input).expect("no match by permutation").into_iter().copied().collect();
+    let mut tree = sum_tree_until_match(to_sum, input).expect("no match in tree");
+    permuted.sort_unstable();
+    tree.sort_unstable();
+    assert_eq!(permuted, tree);
+    if to_sum == 2 {
+        let mut full_tree = get_sum_tree(TARGET, to_sum, input).expect("no match in full tree");
+        full_tree.sort_unstable();
+        assert_eq!(permuted, full_tree);
+    }
+}
+
+/**
+ * Times both strategies on the same 1000-entry report, for pairs and for triples.
+ *
+ * `get_sum_tree` builds the tree from the whole report before searching it. For triples that's every combination of
+ * up to three entries, about 166 million nodes (several GB) for 1000 entries, so it's only timed for pairs. The tree
+ * is timed for both through `SumTree::insert_and_check`, which stops growing the tree at the first match.
+ */
+fn bench_sum_strategies(c: &mut Criterion) {
+    let input = generate_input(INPUT_LEN);
+    let input = &input[..];
+    for to_sum in [2, 3].iter() {
+        check_same_answer(*to_sum, input);
+
+        let mut group = c.benchmark_group(format!("sum of {}", to_sum));
+        group.sample_size(10);
+        group.bench_function("permutations", |b| b.iter(|| get_sum_to(TARGET, *to_sum, black_box(input))));
+        if *to_sum == 2 {
+            group.bench_function("sum tree", |b| b.iter(|| get_sum_tree(TARGET, *to_sum, black_box(input))));
+        }
+        group.bench_function("sum tree, stopping at the first match", |b| b.iter(|| sum_tree_until_match(*to_sum, black_box(input))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_sum_strategies);
+criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,380 @@
+/*!
+ * Finding entries in an expense report that sum up to a target, either by brute force (`Permutations`) or with a
+ * `SumTree`.
+ */
+use std::convert::TryFrom;
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Read};
+
+use advent_common::Puzzle;
+use serde_json::json;
+
+pub mod tree;
+
+pub use tree::SumTree;
+
+/**
+ * slurps data from a file into a vec of integers. One integer per line.
+ */
+pub fn get_input(data: impl Read) -> io::Result<Vec<i64>> {
+    let mut output = Vec::new();
+    for line in BufReader::new(data).lines() {
+        let line: String = line?;
+        match line.parse::<i64>() {
+            Ok(n) => output.push(n),
+            Err(_) => return Err(io::Error::new(io::ErrorKind::InvalidData, "bad data")),
+        }
+    }
+
+    Ok(output)
+}
+
+/**
+ * Like `get_input`, but each line can hold any number of whitespace-separated integers, including none.
+ */
+pub fn get_input_flexible(data: impl Read) -> io::Result<Vec<i64>> {
+    let mut output = Vec::new();
+    for line in BufReader::new(data).lines() {
+        let line: String = line?;
+        for token in line.split_whitespace() {
+            match token.parse::<i64>() {
+                Ok(n) => output.push(n),
+                Err(_) => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("bad data: `{}`", token))),
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/**
+ * Returns a list of elements from `input` of length `to_sum` that sum up to `n`
+ */
+pub fn get_sum_to(n: i64, to_sum: i64, input: &[i64]) -> Option<Vec<&i64>> {
+    Permutations::new(input, to_sum as usize)
+        .find(|perm| perm.iter().copied().sum::<i64>() == n)
+}
+
+pub fn get_sum_tree(n: i64, to_sum: i64, input: &[i64]) -> Option<Vec<i64>> {
+    let mut t = SumTree::new(to_sum);
+    for each in input {
+        t.insert(*each);
+    }
+    t.find(n)
+}
+
+/**
+ * Like `get_sum_tree`, but answers several targets from one tree. Results are in the same order as `targets`.
+ */
+pub fn solve_many(targets: &[i64], to_sum: i64, input: &[i64]) -> Vec<Option<Vec<i64>>> {
+    let mut t = SumTree::new(to_sum);
+    for each in input {
+        t.insert(*each);
+    }
+    targets.iter()
+        .map(|target| t.find(*target))
+        .collect()
+}
+
+/**
+ * Formats the numbers that were found, and their product, as a JSON object.
+ *
+ * ```
+ * assert_eq!(day_1::to_json(&[1721, 299]), r#"{"numbers":[1721,299],"product":514579}"#);
+ * ```
+ */
+pub fn to_json(numbers: &[i64]) -> String {
+    json!({
+        "numbers": numbers,
+        "product": numbers.iter().product::<i64>(),
+    }).to_string()
+}
+
+/**
+ * Day 1 as a `Puzzle`. Part 1 multiplies the two entries that sum to 2020, and part 2 the three entries that do.
+ */
+pub struct ReportRepair;
+
+impl Puzzle for ReportRepair {
+    type Input = Vec<i64>;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(get_input(input)?)
+    }
+    fn part1(input: &Self::Input) -> String {
+        match get_sum_tree(2020, 2, input) {
+            Some(nums) => nums.iter().product::<i64>().to_string(),
+            None => "no matching numbers found".to_string(),
+        }
+    }
+    fn part2(input: &Self::Input) -> String {
+        match get_sum_tree(2020, 3, input) {
+            Some(nums) => nums.iter().product::<i64>().to_string(),
+            None => "no matching numbers found".to_string(),
+        }
+    }
+}
+
+/**
+ * Calculates permutations of a list of elements, with an arbitrary number of elements to permute
+ * 
+ * ```rust
+ * use day_1::Permutations;
+ * assert_eq!(Permutations::new(&[1, 2, 3], 2).collect::<Vec<_>>(),
+ *            vec![vec![&1, &2], vec![&1, &3], vec![&2, &3]]);
+ * ```
+ */
+pub struct Permutations<'a, T> {
+    elements: &'a [T],
+    indexes: Vec<usize>,
+}
+
+impl<'a, T> Permutations<'a, T> {
+    pub fn new(elems: &'a [T], num_indexes: usize) -> Self {
+        let mut indexes: Vec<usize> = (0..).take(num_indexes).collect();
+        indexes.reverse();
+        Permutations {
+            elements: elems,
+            indexes,
+        }
+    }
+    /**
+     * Returns the combination at (0-based) position `k` in iteration order, without iterating over the ones before it.
+     * Uses the combinatorial number system: the number of combinations starting with each element tells us whether
+     * the `k`th one starts there, or further along.
+     */
+    pub fn nth_combination(&self, mut k: usize) -> Option<Vec<&'a T>> {
+        let n = self.elements.len();
+        let r = self.indexes.len();
+        let mut output = Vec::with_capacity(r);
+        let mut next = 0;
+        for remaining in (0..r).rev() {
+            // find the element at this position. Combinations that start with `next` pick the rest from after it.
+            loop {
+                if next >= n {
+                    return None;
+                }
+                // too many combinations to count means there are certainly more than `k` of them
+                match binomial(n - next - 1, remaining) {
+                    Some(starting_here) if k >= starting_here => {
+                        k -= starting_here;
+                        next += 1;
+                    }
+                    _ => break,
+                }
+            }
+            output.push(&self.elements[next]);
+            next += 1;
+        }
+        if k == 0 {
+            Some(output)
+        } else {
+            None
+        }
+    }
+    /**
+     * increments an index of idx `idx` (in the simple case, `self.indexes[idx]++`). Recurses on carry.
+     */
+    fn inc_index(&mut self, idx: usize) -> Option<usize> {
+        if idx >= self.indexes.len() {
+            return None;
+        }
+        self.indexes[idx] += 1;
+        if self.indexes[idx] >= self.elements.len() {
+            match self.inc_index(idx + 1) {
+                Some(new_idx) if new_idx < self.elements.len() => {
+                    // we have a valid index to pull from
+                    self.indexes[idx] = new_idx;
+                    return Some(new_idx + 1);
+                },
+                Some(_) => {
+                    // we need to increment further up the chain
+                    let elem_len = self.elements.len();
+                    if let Some(next_idx) = self.indexes.get_mut(idx + 1) {
+                        *next_idx = elem_len;
+                    }
+                    return self.inc_index(idx);
+                },
+                None => return None,
+            }
+        }
+        Some(self.indexes[idx] + 1)
+    }
+}
+
+/**
+ * The number of ways to choose `k` things from `n`, or `None` if that's too many to fit in a `usize`
+ */
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    // choosing k is the same as leaving out n - k. Using the smaller one keeps the partial products from growing past
+    // the result.
+    let k = k.min(n - k);
+    // each partial product is itself a binomial coefficient, so the division is always exact
+    let count = (0..k).try_fold(1u128, |acc, i| Some(acc.checked_mul((n - i) as u128)? / (i as u128 + 1)))?;
+    usize::try_from(count).ok()
+}
+
+impl<'a, T> Iterator for Permutations<'a, T> {
+    type Item = Vec<&'a T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        // first, map our list of indexes into elements. If any indexes are out of range, bail.
+        let elems: Vec<Option<&T>> = self.indexes.iter()
+            .rev()
+            .map(|idx| self.elements.get(*idx))
+            .collect();
+        if elems.iter().any(Option::is_none) {
+            return None;
+        }
+        // now increment the indexes. We don't care if this goes out of range... yet!
+        self.inc_index(0);
+        // finally take the list of elems we got before, and flatten them to pull the values out of their options.
+        Some(elems.into_iter().flatten().collect())
+    }
+}
+
+#[test]
+fn test_permutator_inc() {
+    let mut p = Permutations::new(&[0, 0, 0], 2);
+    assert_eq!(p.indexes, &[1, 0]);
+    p.inc_index(0).unwrap();
+    assert_eq!(p.indexes, &[2, 0]);
+    p.inc_index(0).unwrap();
+    assert_eq!(p.indexes, &[2, 1]);
+}
+
+#[test]
+fn test_permutations_2() {
+    assert_eq!(Permutations::new(&[1, 2, 3], 2).collect::<Vec<_>>(), vec![vec![&1, &2], vec!(&1, &3), vec!(&2, &3)]);
+    assert!(Permutations::new(&[1], 2).collect::<Vec<_>>().is_empty());
+    assert_eq!(Permutations::new(&[1, 2], 2).collect::<Vec<_>>(), vec![vec!(&1, &2)]);
+}
+
+#[test]
+fn test_permutations_3() {
+    assert_eq!(Permutations::new(&[1, 2, 3, 4], 3).collect::<Vec<_>>(),
+                vec![vec![&1, &2, &3], vec![&1, &2, &4], vec![&1, &3, &4], vec![&2, &3, &4]]);
+    assert!(Permutations::new(&[1], 3).collect::<Vec<_>>().is_empty());
+    assert!(Permutations::new(&[1, 2], 3).collect::<Vec<_>>().is_empty());
+    assert_eq!(Permutations::new(&[1, 2, 3], 3).collect::<Vec<_>>(), vec![vec!(&1, &2, &3)]);
+}
+
+#[test]
+fn test_nth_combination() {
+    let elems = [1, 2, 3, 4, 5];
+    let p = Permutations::new(&elems, 2);
+    assert_eq!(p.nth_combination(3), Permutations::new(&elems, 2).nth(3));
+    assert_eq!(p.nth_combination(3), Some(vec![&1, &5]));
+
+    for r in 1..=5 {
+        let p = Permutations::new(&elems, r);
+        let all: Vec<_> = Permutations::new(&elems, r).collect();
+        for (k, combination) in all.iter().enumerate() {
+            assert_eq!(p.nth_combination(k).as_ref(), Some(combination));
+        }
+        assert_eq!(p.nth_combination(all.len()), None);
+    }
+    assert_eq!(Permutations::new(&elems, 6).nth_combination(0), None);
+
+    // far more combinations than fit in a usize
+    let elems: Vec<usize> = (0..100).collect();
+    let p = Permutations::new(&elems, 50);
+    assert_eq!(p.nth_combination(0), Some(elems[..50].iter().collect()));
+    assert_eq!(p.nth_combination(1), Some(elems[..49].iter().chain(Some(&elems[50])).collect()));
+}
+
+#[test]
+fn test_binomial() {
+    assert_eq!(binomial(5, 2), Some(10));
+    assert_eq!(binomial(5, 0), Some(1));
+    assert_eq!(binomial(5, 5), Some(1));
+    assert_eq!(binomial(2, 3), Some(0));
+    assert_eq!(binomial(1000, 3), Some(166_167_000));
+    // small results from big inputs don't overflow along the way
+    assert_eq!(binomial(100_000, 99_999), Some(100_000));
+    assert_eq!(binomial(62, 31), Some(465_428_353_255_261_088));
+    // but results too big for a usize are reported
+    assert_eq!(binomial(100, 50), None);
+}
+
+#[test]
+fn test_get_sum_to() {
+    assert_eq!(get_sum_to(5, 2, &[1, 2, 3]), Some(vec!(&2, &3)));
+    assert_eq!(get_sum_to(5, 2, &[1, 2, 3, 4]), Some(vec!(&1, &4)));
+    assert_eq!(get_sum_to(5, 2, &[1, 2, 4]), Some(vec!(&1, &4)));
+    assert_eq!(get_sum_to(10, 2, &[1, 2, 4]), None);
+
+    assert_eq!(get_sum_to(20, 3, &[1, 2, 4, 6, 10]), Some(vec![&4, &6, &10]));
+}
+
+#[test]
+fn test_get_sum_tree() {
+    assert_eq!(get_sum_tree(5, 2, &[1, 2, 3]), Some(vec!(3, 2)));
+    assert_eq!(get_sum_tree(5, 2, &[1, 2, 3, 4]), Some(vec!(4, 1)));
+    assert_eq!(get_sum_tree(5, 2, &[1, 2, 4]), Some(vec!(4, 1)));
+    assert_eq!(get_sum_tree(10, 2, &[1, 2, 4]), None);
+
+    assert_eq!(get_sum_tree(20, 3, &[1, 2, 4, 6, 10]), Some(vec![10, 6, 4]));
+}
+
+#[test]
+fn test_solve_many() {
+    let input = [1721, 979, 366, 299, 675, 1456];
+    assert_eq!(solve_many(&[2020, 5], 2, &input), vec![get_sum_tree(2020, 2, &input), None]);
+    assert_eq!(solve_many(&[2020, 5], 2, &input)[0], Some(vec![299, 1721]));
+    assert!(solve_many(&[], 2, &input).is_empty());
+}
+
+#[test]
+fn test_to_json() {
+    let numbers = get_sum_tree(2020, 3, &[1721, 979, 366, 299, 675, 1456]).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&to_json(&numbers)).unwrap();
+    assert_eq!(parsed["numbers"], json!(numbers));
+    assert_eq!(parsed["product"], 241861950);
+}
+
+#[test]
+fn test_get_input_flexible() {
+    use std::io::Cursor;
+    assert_eq!(get_input_flexible(Cursor::new("1 2 3\n4 5")).unwrap(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(get_input_flexible(Cursor::new("  -1\t2\n\n3\n")).unwrap(), vec![-1, 2, 3]);
+    assert!(get_input_flexible(Cursor::new("1 two 3")).is_err());
+    // get_input is still one number per line
+    assert!(get_input(Cursor::new("1 2 3\n4 5")).is_err());
+}
+
+#[test]
+fn test_part_1() {
+    use std::io::Cursor;
+    let data = include_str!("../input.txt");
+    let data = get_input(Cursor::new(data)).unwrap();
+    let numbers = get_sum_to(2020, 2, &data).unwrap();
+
+    assert_eq!(numbers.len(), 2);
+    assert!(numbers[0] != numbers[1]);
+    assert_eq!(numbers.iter().copied().sum::<i64>(), 2020);
+}
+
+#[test]
+fn test_part_2() {
+    use std::io::Cursor;
+    let data = include_str!("../input.txt");
+    let data = get_input(Cursor::new(data)).unwrap();
+    let numbers = get_sum_to(2020, 3, &data).unwrap();
+
+    assert_eq!(numbers.len(), 3);
+    assert!(numbers[0] != numbers[1]);
+    assert!(numbers[0] != numbers[2]);
+    assert_eq!(numbers.iter().copied().sum::<i64>(), 2020);
+}
+
+#[test]
+fn test_puzzle() {
+    use std::io::Cursor;
+    let input = ReportRepair::parse(Cursor::new("1721\n979\n366\n299\n675\n1456")).unwrap();
+    assert_eq!(ReportRepair::part1(&input), "514579");
+    assert_eq!(ReportRepair::part2(&input), "241861950");
+    assert_eq!(ReportRepair::part1(&vec![1, 2]), "no matching numbers found");
+}
\ No newline at end of file
@@ -38,6 +38,42 @@
         };
         self.children.push(new_entry);
     }
+    /**
+     * Inserts a value, and checks whether it completed a set of numbers that sum up to `target`.
+     * If it did, that set is returned (in the same order as `find`), and there's no need to insert any more values.
+     */
+    pub fn insert_and_check(&mut self, value: i64, target: i64) -> Option<Vec<i64>> {
+        if self.depth <= 0 {
+            // limited by the desired tree depth
+            return None;
+        }
+
+        // add a new node to all children, keeping the first solution any of them completes
+        let mut found = None;
+        for child in self.children.iter_mut() {
+            let completed = child.insert_and_check(value, target);
+            if found.is_none() {
+                found = completed;
+            }
+        }
+        // the new node here could be the last number of a solution too
+        if found.is_none() && self.depth == 1 && self.sum + value == target {
+            found = Some(vec![value]);
+        }
+
+        self.children.push(SumTree {
+            number: value,
+            sum: self.sum + value,
+            depth: self.depth - 1,
+            ..Default::default()
+        });
+        found.map(|mut v| {
+            if self.number != 0 {
+                v.push(self.number);
+            }
+            v
+        })
+    }
     pub fn find(&self, sum: i64) -> Option<Vec<i64>> {
         if self.depth == 0 && self.sum == sum {
             // hey look we found it!
@@ -171,4 +207,20 @@
      
      assert_eq!(t.find(6), Some(vec![3, 2, 1]));
      assert_eq!(t.find(9), Some(vec![4, 3, 2]));
+ }
+
+ #[test]
+ fn test_tree_insert_and_check() {
+     let mut t = SumTree::new(2);
+     assert_eq!(t.insert_and_check(1721, 2020), None);
+     assert_eq!(t.insert_and_check(979, 2020), None);
+     assert_eq!(t.insert_and_check(366, 2020), None);
+     // 675 and 1456 never need to go in
+     assert_eq!(t.insert_and_check(299, 2020), Some(vec![299, 1721]));
+
+     let mut t = SumTree::new(3);
+     let found = [1721, 979, 366, 299, 675, 1456].iter()
+         .position(|value| t.insert_and_check(*value, 2020).is_some());
+     assert_eq!(found, Some(4));
+     assert_eq!(t.find(2020), Some(vec![675, 366, 979]));
  }
\ No newline at end of file
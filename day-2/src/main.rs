@@ -1,5 +1,6 @@
 #[macro_use] extern crate lazy_static;
 
+use std::collections::BTreeMap;
 use std::{error::Error, ops::{RangeInclusive}};
 use std::io::{self, Read, BufRead, BufReader};
 use std::fs::File;
@@ -21,18 +22,14 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .help("policy to use"))
             .get_matches();
     let variant = match matches.value_of("policy") {
-        Some("range") | None => PolicyVariant::OccurrenceRange,
+        Some("range") | None => PolicyVariant::OccurrenceRange(CountMode::default()),
         Some("xor") => PolicyVariant::XorPosition,
         Some(bad) => panic!("invalid value to --policy: {}", bad),
     };
     let f = File::open(matches.value_of("input").unwrap())?;
-    let input: Vec<String> = get_input(f)?;
-    let passwords: Vec<(String, Policy)> = parse_input(input).unwrap();
-    let valid: Vec<(String, Policy)> = passwords.into_iter().filter(|(pass, policy)| {
-        policy.is_valid(&pass, variant)
-    }).collect();
-    
-    println!("number of valid passwords: {}", valid.len());
+    let num_valid = count_valid(BufReader::new(f), variant)?;
+
+    println!("number of valid passwords: {}", num_valid);
     Ok(())
 }
 
@@ -45,16 +42,38 @@ pub struct Policy {
 #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub enum PolicyVariant {
     /// letters must occur within the range given
-    OccurrenceRange,
+    OccurrenceRange(CountMode),
     /// a letter must occur in either the start or end position, not both
     XorPosition,
 }
 
+/// How occurrences of a policy's letters are counted in a password
+#[derive(Debug, Default, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum CountMode {
+    /// matches can't share characters, so "aa" occurs once in "aaa"
+    #[default]
+    NonOverlapping,
+    /// matches may share characters, so "aa" occurs twice in "aaa"
+    Overlapping,
+}
+
+impl CountMode {
+    /// Counts the occurrences of `needle` in `haystack`
+    pub fn count(self, haystack: &str, needle: &str) -> usize {
+        match self {
+            Self::NonOverlapping => haystack.matches(needle).count(),
+            Self::Overlapping => haystack.char_indices()
+                .filter(|(idx, _)| haystack[*idx..].starts_with(needle))
+                .count(),
+        }
+    }
+}
+
 impl Policy {
     pub fn is_valid(&self, password: &str, variant: PolicyVariant) -> bool {
         match variant {
-            PolicyVariant::OccurrenceRange => {
-                let policy_occs = password.matches(&self.letter).count();
+            PolicyVariant::OccurrenceRange(mode) => {
+                let policy_occs = mode.count(password, &self.letter);
                 self.occurrences.contains(&(policy_occs as i64))
             },
             PolicyVariant::XorPosition => {
@@ -96,7 +115,7 @@ impl FromStr for Policy {
                 letter: c.to_string(),
                 occurrences: l..=h,
             }),
-            _ => Err(format!("failed to parse into a policy").into())
+            _ => Err("failed to parse into a policy".into())
         }
     }
 }
@@ -110,14 +129,46 @@ pub fn get_input(reader: impl Read) -> io::Result<Vec<String>> {
 
 pub fn parse_input(input: impl IntoIterator<Item=String>) -> Result<Vec<(String, Policy)>, Box<dyn Error>> {
     let mut output = Vec::new();
-    for line in input {
-        let password = get_password_from_line(&line)?;
-        let policy = Policy::from_str(&line)?;
-        output.push((password.to_string(), policy));
+    for (idx, line) in input.into_iter().enumerate() {
+        output.push(parse_line(idx, &line)?);
     }
     Ok(output)
 }
 
+/**
+ * Counts the valid passwords in the input, one line at a time, without keeping them all around
+ */
+pub fn count_valid(input: impl BufRead, variant: PolicyVariant) -> Result<usize, Box<dyn Error>> {
+    let mut num_valid = 0;
+    for (idx, line) in input.lines().enumerate() {
+        let (password, policy) = parse_line(idx, &line?)?;
+        if policy.is_valid(&password, variant) {
+            num_valid += 1;
+        }
+    }
+    Ok(num_valid)
+}
+
+/**
+ * Counts the valid passwords for each policy letter. Letters with no valid passwords are left out.
+ */
+pub fn valid_by_letter(passwords: &[(String, Policy)], variant: PolicyVariant) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for (password, policy) in passwords {
+        if policy.is_valid(password, variant) {
+            *counts.entry(policy.letter.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Parses the password and policy out of the line at (0-based) `idx`, noting the line in any error
+fn parse_line(idx: usize, line: &str) -> Result<(String, Policy), Box<dyn Error>> {
+    get_password_from_line(line)
+        .and_then(|password| Ok((password.to_string(), Policy::from_str(line)?)))
+        .map_err(|e| format!("line {}: `{}`: {}", idx + 1, line, e).into())
+}
+
 pub fn get_password_from_line(line: &str) -> Result<&str, Box<dyn Error>> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r".*:\s+(?P<password>\w+)").unwrap();
@@ -137,7 +188,7 @@ fn test_part_1() {
     let data = get_input(Cursor::new(data)).unwrap();
     let passwords: Vec<(String, Policy)> = parse_input(data).unwrap();
     let valid: Vec<(String, Policy)> = passwords.into_iter().filter(|(pass, policy)| {
-        policy.is_valid(&pass, PolicyVariant::OccurrenceRange)
+        policy.is_valid(pass, PolicyVariant::OccurrenceRange(CountMode::NonOverlapping))
     }).collect();
     assert_eq!(valid.len(), 638);
 }
@@ -149,7 +200,7 @@ fn test_part_2() {
     let data = get_input(Cursor::new(data)).unwrap();
     let passwords: Vec<(String, Policy)> = parse_input(data).unwrap();
     let valid: Vec<(String, Policy)> = passwords.into_iter().filter(|(pass, policy)| {
-        policy.is_valid(&pass, PolicyVariant::XorPosition)
+        policy.is_valid(pass, PolicyVariant::XorPosition)
     }).collect();
     assert_eq!(valid.len(), 699);
 }
@@ -182,14 +233,70 @@ fn test_parse_input() {
     ])
 }
 
+#[test]
+fn test_parse_input_line_numbers() {
+    let lines = vec!["1-3 a: abcde".to_string(), "1-3 b cdefg".to_string(), "2-9 c: ccccccccc".to_string()];
+    let err = parse_input(lines).unwrap_err().to_string();
+    assert!(err.starts_with("line 2: `1-3 b cdefg`"), "{}", err);
+
+    let lines = vec!["1-3 a: abcde".to_string(), "2-9 c: ccccccccc".to_string()];
+    assert_eq!(parse_input(lines).unwrap().len(), 2);
+}
+
+#[test]
+fn test_count_valid() {
+    use std::io::Cursor;
+    let data = include_str!("../input.txt");
+    for variant in [PolicyVariant::OccurrenceRange(CountMode::NonOverlapping), PolicyVariant::XorPosition].iter() {
+        let passwords = parse_input(get_input(Cursor::new(data)).unwrap()).unwrap();
+        let collected = passwords.iter().filter(|(pass, policy)| policy.is_valid(pass, *variant)).count();
+        assert_eq!(count_valid(Cursor::new(data), *variant).unwrap(), collected);
+    }
+    assert_eq!(count_valid(Cursor::new(data), PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)).unwrap(), 638);
+
+    let err = count_valid(Cursor::new("1-3 a: abcde\n1-3 b cdefg"), PolicyVariant::XorPosition).unwrap_err();
+    assert!(err.to_string().starts_with("line 2:"));
+}
+
+#[test]
+fn test_count_mode() {
+    assert_eq!(CountMode::NonOverlapping.count("aaa", "aa"), 1);
+    assert_eq!(CountMode::Overlapping.count("aaa", "aa"), 2);
+    assert_eq!(CountMode::Overlapping.count("abab", "ab"), 2);
+    assert_eq!(CountMode::Overlapping.count("bcd", "a"), 0);
+    assert_eq!(CountMode::default(), CountMode::NonOverlapping);
+
+    let policy = Policy { letter: "aa".to_string(), occurrences: 2..=2 };
+    assert!(!policy.is_valid("aaa", PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)));
+    assert!(policy.is_valid("aaa", PolicyVariant::OccurrenceRange(CountMode::Overlapping)));
+}
+
 #[test]
 fn test_is_policy_valid() {
-    assert!(Policy { letter: "a".to_string(), occurrences: 1..=3 }.is_valid("abcde", PolicyVariant::OccurrenceRange));
-    assert!(Policy { letter: "a".to_string(), occurrences: 1..=3 }.is_valid("aaabcde", PolicyVariant::OccurrenceRange));
-    assert!(! Policy { letter: "b".to_string(), occurrences: 1..=3 }.is_valid("cdefg", PolicyVariant::OccurrenceRange));
-    assert!(Policy { letter: "c".to_string(), occurrences: 2..=9 }.is_valid("ccccccccc", PolicyVariant::OccurrenceRange));
+    assert!(Policy { letter: "a".to_string(), occurrences: 1..=3 }.is_valid("abcde", PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)));
+    assert!(Policy { letter: "a".to_string(), occurrences: 1..=3 }.is_valid("aaabcde", PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)));
+    assert!(! Policy { letter: "b".to_string(), occurrences: 1..=3 }.is_valid("cdefg", PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)));
+    assert!(Policy { letter: "c".to_string(), occurrences: 2..=9 }.is_valid("ccccccccc", PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)));
 
     assert!(Policy { letter: "a".to_string(), occurrences: 1..=3 }.is_valid("abcde", PolicyVariant::XorPosition));
     assert!(! Policy { letter: "b".to_string(), occurrences: 1..=3 }.is_valid("cdefg", PolicyVariant::XorPosition));
     assert!(! Policy { letter: "c".to_string(), occurrences: 2..=9 }.is_valid("ccccccccc", PolicyVariant::XorPosition));
+}
+
+#[test]
+fn test_valid_by_letter() {
+    let input = vec![
+        "1-3 a: abcde".to_string(),
+        "1-3 b: cdefg".to_string(),
+        "1-2 a: aab".to_string(),
+        "2-9 c: ccccccccc".to_string(),
+        "1-1 a: bcd".to_string(),
+        "1-3 b: bbx".to_string(),
+    ];
+    let passwords = parse_input(input).unwrap();
+    assert_eq!(valid_by_letter(&passwords, PolicyVariant::OccurrenceRange(CountMode::NonOverlapping)),
+               vec![("a".to_string(), 2), ("b".to_string(), 1), ("c".to_string(), 1)].into_iter().collect::<BTreeMap<_, _>>());
+    assert_eq!(valid_by_letter(&passwords, PolicyVariant::XorPosition),
+               vec![("a".to_string(), 1), ("b".to_string(), 1)].into_iter().collect::<BTreeMap<_, _>>());
+    assert!(valid_by_letter(&[], PolicyVariant::XorPosition).is_empty());
 }
\ No newline at end of file
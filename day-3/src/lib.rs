@@ -0,0 +1,580 @@
+/*!
+ * Counting the trees a toboggan hits on its way down a field that repeats off to the right.
+ */
+use std::error::Error;
+use std::fmt;
+use std::io::{Read, BufRead, BufReader};
+use std::iter::FromIterator;
+use std::ops::Index;
+use std::str::FromStr;
+use std::thread;
+
+/**
+ * Counts the number of trees encountered while toboggoning down a field
+ *
+ * ```
+ * use day_3::{count_trees, Field};
+ * let field: Field = "#..\n.#.\n...".parse().unwrap();
+ * assert_eq!(count_trees(&field, 1, 1), 2);
+ * ```
+ */
+pub fn count_trees(field: &Field, slope_right: usize, slope_down: usize) -> u64 {
+    count_trees_from(field, 0, slope_right, slope_down)
+}
+
+/**
+ * Like `count_trees`, but the toboggan sets off from column `start_col` of the top row instead of the left edge
+ */
+pub fn count_trees_from(field: &Field, start_col: usize, slope_right: usize, slope_down: usize) -> u64 {
+    let mut column = start_col;
+    let mut trees = 0;
+    for row in field.into_iter().step_by(slope_down) {
+        if row.get(column) == Some(Coordinate::Tree) {
+            trees += 1;
+        }
+        column += slope_right;
+    }
+    trees
+}
+
+/**
+ * Counts trees for each of `slopes` at once, as (right, down) pairs. The field's rows are split into contiguous chunks,
+ * one per thread, and each thread counts every slope over its chunk.
+ *
+ * A toboggan going (right, down) lands on rows that are a multiple of `down`, and on row `down * k` it's in column
+ * `right * k`, so a chunk can work out where each slope enters it without walking the rows before it.
+ *
+ * A slope that never moves down is an error, since the toboggan would never leave the top row.
+ */
+pub fn count_trees_parallel(field: &Field, slopes: &[(usize, usize)]) -> Result<Vec<u64>, Box<dyn Error>> {
+    if let Some(slope) = slopes.iter().find(|(_, down)| *down == 0) {
+        return Err(format!("slope {:?} never moves down", slope).into());
+    }
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let chunk_size = field.len().div_ceil(threads).max(1);
+    let partials: Vec<Vec<u64>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..field.len())
+            .step_by(chunk_size)
+            .map(|start| {
+                let end = (start + chunk_size).min(field.len());
+                scope.spawn(move || {
+                    slopes.iter()
+                        .map(|&(right, down)| {
+                            // the first row at or after `start` that this slope lands on
+                            let first = start.div_ceil(down) * down;
+                            (first..end)
+                                .step_by(down)
+                                .filter(|&row| field[row].get(row / down * right) == Some(Coordinate::Tree))
+                                .count() as u64
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    let mut totals = vec![0; slopes.len()];
+    for partial in partials {
+        for (total, count) in totals.iter_mut().zip(partial) {
+            *total += count;
+        }
+    }
+    Ok(totals)
+}
+
+/**
+ * Like `count_trees`, but slopes can go left (negative `right`) or up (negative `down`). Going up starts from the
+ * bottom row instead of the top. Either way, the walk stops when it goes off the top or bottom of the field.
+ */
+pub fn count_trees_signed(field: &Field, right: isize, down: isize) -> u64 {
+    let mut row: isize = if down < 0 { field.len() as isize - 1 } else { 0 };
+    let mut column: isize = 0;
+    let mut trees = 0;
+    while row >= 0 && (row as usize) < field.len() {
+        let width = field[row as usize].columns.len() as isize;
+        if width > 0 && field[row as usize][column.rem_euclid(width) as usize] == Coordinate::Tree {
+            trees += 1;
+        }
+        if down == 0 {
+            // we'd never leave this row
+            break;
+        }
+        row += down;
+        column += right;
+    }
+    trees
+}
+
+/**
+ * Implementation of a field with trees and such
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    rows: Vec<Row>,
+}
+
+impl Field {
+    /// Construct a field from a reader. Each line is another row in the field.
+    pub fn from_reader(r: impl Read) -> Result<Self, Box<dyn Error>> {
+        let mut rows = Vec::new();
+        for line in BufReader::new(r).lines() {
+            let line: String = line?;
+            let col = Row::from_str(&line)?;
+            rows.push(col);
+        }
+        Ok(Self { rows })
+    }
+    /// Adds a row to the bottom of the field
+    pub fn push_row(&mut self, row: Row) {
+        self.rows.push(row);
+    }
+    /// Swaps rows and columns, so the first column becomes the first row. Every row must be the same length.
+    pub fn transpose(&self) -> Result<Field, Box<dyn Error>> {
+        let width = self.rows.first().map(|row| row.columns.len()).unwrap_or(0);
+        if let Some(idx) = self.rows.iter().position(|row| row.columns.len() != width) {
+            return Err(format!("can't transpose a ragged field: row {} has {} columns, expected {}",
+                               idx, self.rows[idx].columns.len(), width).into());
+        }
+        Ok((0..width)
+            .map(|col| Row { columns: self.rows.iter().map(|row| row.columns[col]).collect() })
+            .collect())
+    }
+    /// Returns the number of rows in the field
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+    /// Returns true if the field has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+    /**
+     * Returns the (up to) eight cells around (`row`, `col`), along with what's in them.
+     *
+     * Columns wrap around like they do when indexing a row, so there's always a cell to the left and right. Rows
+     * don't wrap: cells above the top or below the bottom of the field are left out.
+     */
+    pub fn neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize, Coordinate)> {
+        let mut found = Vec::new();
+        let first_row = if row == 0 { 0 } else { row - 1 };
+        for neighbor_row in first_row..=row + 1 {
+            let each_row = match self.rows.get(neighbor_row) {
+                Some(r) => r,
+                None => continue,
+            };
+            let width = each_row.columns.len();
+            if width == 0 {
+                continue;
+            }
+            // on rows only one or two cells wide, the columns to the left and right wrap onto the same cell
+            let mut neighbor_cols = Vec::with_capacity(3);
+            for c in [col + width - 1, col, col + 1].iter().map(|c| c % width) {
+                if !neighbor_cols.contains(&c) {
+                    neighbor_cols.push(c);
+                }
+            }
+            for neighbor_col in neighbor_cols {
+                if neighbor_row == row && neighbor_col == col % width {
+                    continue;
+                }
+                found.push((neighbor_row, neighbor_col, each_row[neighbor_col]));
+            }
+        }
+        found
+    }
+}
+
+impl FromStr for Field {
+    type Err = Box<dyn Error>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines().map(Row::from_str).collect()
+    }
+}
+
+impl FromIterator<Row> for Field {
+    fn from_iter<I: IntoIterator<Item=Row>>(iter: I) -> Self {
+        Self { rows: iter.into_iter().collect() }
+    }
+}
+
+impl Index<usize> for Field {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Self::Output {
+        self.rows.index(idx)
+    }
+}
+
+impl IntoIterator for Field {
+    type Item = Row;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Field {
+    type Item = &'a Row;
+    type IntoIter = std::slice::Iter<'a, Row>;
+
+    fn into_iter(self) -> Self::IntoIter
+        where Self: 'a
+    {
+        self.rows.iter()
+    }
+}
+
+/**
+ * A Row in the Field. Rows are the vertical coordinates of the field.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// the columns in this row
+    columns: Vec<Coordinate>,
+}
+
+impl FromStr for Row {
+    type Err = Box<dyn Error>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Parse the line given via Coordinate's construction function
+        let columns: Result<Vec<Coordinate>, _> = s.chars().map(Coordinate::from_char).collect();
+        let columns = columns?;
+        Ok(Row {
+            columns
+        })
+    }
+}
+
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for column in self.columns.iter() {
+            write!(f, "{}", column)?;
+        }
+        Ok(())
+    }
+}
+
+impl Row {
+    /// Like indexing, the column wraps around if it's past the end of the row. Returns `None` if the row is empty.
+    pub fn get(&self, idx: usize) -> Option<Coordinate> {
+        if self.columns.is_empty() {
+            None
+        } else {
+            Some(self.columns[idx % self.columns.len()])
+        }
+    }
+}
+
+impl Index<usize> for Row {
+    type Output = Coordinate;
+    fn index(&self, idx: usize) -> &Self::Output {
+        // row's indexing wraps if the index is larger than the number of columns available.
+        let idx_mod = idx % self.columns.len();
+        &self.columns[idx_mod]
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Coordinate {
+    /// An open space
+    Open,
+    /// A tree. Wouldn't want to hit that
+    Tree,
+}
+
+impl Coordinate {
+    /**
+     * Construct a Coordinate from a character.
+     */
+    pub fn from_char(c: char) -> Result<Self, Box<dyn Error>> {
+        match c {
+            '.' => Ok(Self::Open),
+            '#' => Ok(Self::Tree),
+            e => Err(format!("invalid character for coordinate: '{}'", e).into())
+        }
+    }
+}
+
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "."),
+            Self::Tree => write!(f, "#"),
+        }
+    }
+}
+
+#[test]
+fn test_part_1() {
+    use std::io::Cursor;
+    let input = include_str!("../input.txt");
+    let field = Field::from_reader(Cursor::new(input)).unwrap();
+
+    let num_trees = count_trees(&field, 3, 1);
+    assert_eq!(num_trees, 162);
+}
+
+#[test]
+fn test_part_2() {
+    use std::io::Cursor;
+    let input = include_str!("../input.txt");
+    let field = Field::from_reader(Cursor::new(input)).unwrap();
+    let slopes = [
+        (1, 1),
+        (3, 1),
+        (5, 1),
+        (7, 1),
+        (1, 2),
+    ];
+    let nums_trees: Vec<u64> = slopes.iter()
+        .map(|(right, down)| count_trees(&field, *right, *down))
+        .collect();
+    assert_eq!(nums_trees, vec![80, 162, 77, 83, 37]);
+    assert_eq!(nums_trees.iter().copied().product::<u64>(), 3064612320);
+}
+
+
+#[test]
+fn test_count_trees_from() {
+    use std::io::Cursor;
+    let input = "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n..#.##.....\n\
+                 .#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n.#..#...#.#";
+    let field = Field::from_reader(Cursor::new(input)).unwrap();
+    assert_eq!(count_trees(&field, 3, 1), 7);
+    assert_eq!(count_trees_from(&field, 0, 3, 1), 7);
+    assert_eq!(count_trees_from(&field, 1, 3, 1), 4);
+    // the starting column wraps like any other
+    assert_eq!(count_trees_from(&field, 12, 3, 1), 4);
+}
+
+#[test]
+fn test_count_trees_signed() {
+    let field = Field::from_str("#...\n..#.\n.#..\n....").unwrap();
+    assert_eq!(count_trees_signed(&field, 1, 1), count_trees(&field, 1, 1));
+    assert_eq!(count_trees_signed(&field, 1, 1), 1);
+    // from the bottom left, going up and to the right
+    assert_eq!(count_trees_signed(&field, 1, -1), 2);
+    // going left wraps around to the right edge
+    assert_eq!(count_trees_signed(&field, -1, -1), 1);
+    assert_eq!(count_trees_signed(&field, 0, -2), 0);
+    assert_eq!(count_trees_signed(&Field::from_str("").unwrap(), 1, -1), 0);
+}
+
+#[test]
+fn test_coordinate_from_char() {
+    assert_eq!(Coordinate::from_char('.').unwrap(), Coordinate::Open);
+    assert_eq!(Coordinate::from_char('#').unwrap(), Coordinate::Tree);
+    assert!(Coordinate::from_char('n').is_err());
+}
+
+#[test]
+fn test_row_from_str() {
+    assert_eq!(Row::from_str(".##.").unwrap(), Row { columns: vec![Coordinate::Open, Coordinate::Tree, Coordinate::Tree, Coordinate::Open]});
+}
+
+#[test]
+fn test_row_display() {
+    assert_eq!(Coordinate::Tree.to_string(), "#");
+    assert_eq!(Coordinate::Open.to_string(), ".");
+
+    let row = Row { columns: vec![Coordinate::Open, Coordinate::Tree, Coordinate::Tree, Coordinate::Open, Coordinate::Tree]};
+    assert_eq!(row.to_string(), ".##.#");
+    assert_eq!(Row::from_str(&row.to_string()).unwrap(), row);
+}
+
+#[test]
+fn test_field_neighbors() {
+    use std::io::Cursor;
+    let field = Field::from_reader(Cursor::new("#..#\n.#..\n..##")).unwrap();
+
+    let center = field.neighbors(1, 1);
+    assert_eq!(center, vec![
+        (0, 0, Coordinate::Tree), (0, 1, Coordinate::Open), (0, 2, Coordinate::Open),
+        (1, 0, Coordinate::Open), (1, 2, Coordinate::Open),
+        (2, 0, Coordinate::Open), (2, 1, Coordinate::Open), (2, 2, Coordinate::Tree),
+    ]);
+
+    // the top row has nothing above it, and the left edge wraps around to the right
+    let corner = field.neighbors(0, 0);
+    assert_eq!(corner, vec![
+        (0, 3, Coordinate::Tree), (0, 1, Coordinate::Open),
+        (1, 3, Coordinate::Open), (1, 0, Coordinate::Open), (1, 1, Coordinate::Tree),
+    ]);
+    assert_eq!(field.neighbors(2, 3).len(), 5);
+
+    // narrow fields don't hand back the same cell twice, or the cell itself
+    let one_wide = Field::from_reader(Cursor::new("#\n.\n#")).unwrap();
+    assert_eq!(one_wide.neighbors(1, 0), vec![(0, 0, Coordinate::Tree), (2, 0, Coordinate::Tree)]);
+    let two_wide = Field::from_reader(Cursor::new("#.\n.#\n##")).unwrap();
+    assert_eq!(two_wide.neighbors(1, 0), vec![
+        (0, 1, Coordinate::Open), (0, 0, Coordinate::Tree),
+        (1, 1, Coordinate::Tree),
+        (2, 1, Coordinate::Tree), (2, 0, Coordinate::Tree),
+    ]);
+}
+
+#[test]
+fn test_row_get() {
+    use std::io::Cursor;
+    let row = Row { columns: vec![Coordinate::Tree, Coordinate::Open]};
+    assert_eq!(row.get(0), Some(Coordinate::Tree));
+    assert_eq!(row.get(1), Some(Coordinate::Open));
+    assert_eq!(row.get(2), Some(Coordinate::Tree));
+
+    let empty = Row::from_str("").unwrap();
+    assert_eq!(empty.get(0), None);
+    assert_eq!(empty.get(5), None);
+
+    let field = Field::from_reader(Cursor::new("#.\n\n#.")).unwrap();
+    assert_eq!(count_trees(&field, 1, 1), 2);
+    // the cells to the left and right of (0, 0) are both (0, 1), and the row below is empty
+    assert_eq!(field.neighbors(0, 0), vec![(0, 1, Coordinate::Open)]);
+}
+
+#[test]
+fn test_field_collect() {
+    use std::io::Cursor;
+    let text = "#..#\n.#..\n..##";
+    let expected = Field::from_reader(Cursor::new(text)).unwrap();
+
+    let collected: Field = text.lines().map(|line| Row::from_str(line).unwrap()).collect();
+    assert_eq!(collected, expected);
+    assert_eq!(Field::from_str(text).unwrap(), expected);
+    Field::from_str("#..#\n.x..").unwrap_err();
+
+    let mut pushed: Field = Vec::new().into_iter().collect();
+    assert!(pushed.is_empty());
+    for line in text.lines() {
+        pushed.push_row(Row::from_str(line).unwrap());
+    }
+    assert_eq!(pushed, expected);
+}
+
+#[test]
+fn test_field_transpose() {
+    let field = Field::from_str("#..\n.##").unwrap();
+    let transposed = field.transpose().unwrap();
+    assert_eq!(transposed, Field::from_str("#.\n.#\n.#").unwrap());
+    assert_eq!(transposed.transpose().unwrap(), field);
+
+    // going straight down a column passes the same trees as going straight across that row of the transpose
+    for col in 0..3 {
+        let across = transposed[col].to_string().matches('#').count() as u64;
+        assert_eq!(count_trees_from(&field, col, 0, 1), across);
+    }
+
+    assert!(Field::from_str("").unwrap().transpose().unwrap().is_empty());
+    Field::from_str("#..\n.#").unwrap().transpose().unwrap_err();
+}
+
+#[test]
+fn test_field_construct() {
+    use std::io::Cursor;
+    let input = Cursor::new(
+        "#..#\n####\n.#..\n"
+    );
+    let field = Field::from_reader(input).unwrap();
+    assert_eq!(field, Field {
+        rows: vec![
+            Row { columns: vec![Coordinate::Tree, Coordinate::Open, Coordinate::Open, Coordinate::Tree], },
+            Row { columns: vec![Coordinate::Tree, Coordinate::Tree, Coordinate::Tree, Coordinate::Tree], },
+            Row { columns: vec![Coordinate::Open, Coordinate::Tree, Coordinate::Open, Coordinate::Open], },
+        ]
+    })
+}
+
+#[test]
+fn test_field_index() {
+    use std::io::Cursor;
+    let input = Cursor::new(
+        "#..#\n####\n.#..\n"
+    );
+    let field = Field::from_reader(input).unwrap();
+    assert_eq!(field[0][0], Coordinate::Tree);
+}
+
+#[test]
+fn test_column_index() {
+    let row = Row { columns: vec![Coordinate::Tree, Coordinate::Open, Coordinate::Open, Coordinate::Open ]};
+    assert_eq!(row[0], Coordinate::Tree);
+    assert_eq!(row[1], Coordinate::Open);
+    assert_eq!(row[2], Coordinate::Open);
+    assert_eq!(row[3], Coordinate::Open);
+
+    assert_eq!(row[4], Coordinate::Tree);
+    assert_eq!(row[5], Coordinate::Open);
+}
+
+#[test]
+fn test_count_trees() {
+    use std::io::Cursor;
+    let input = Cursor::new(
+        ["..##.......",
+             "#...#...#..",
+             ".#....#..#.",
+             "..#.#...#.#",
+             ".#...##..#.",
+             "..#.##.....",
+             ".#.#.#....#",
+             ".#........#",
+             "#.##...#...",
+             "#...##....#",
+             ".#..#...#.#"].join("\n")
+    );
+    let field = Field::from_reader(input).unwrap();
+    assert_eq!(count_trees(&field, 3, 1), 7);
+}
+
+#[test]
+fn test_count_trees_vary_slope() {
+    use std::io::Cursor;
+    let input = Cursor::new(
+        ["..##.......",
+             "#...#...#..",
+             ".#....#..#.",
+             "..#.#...#.#",
+             ".#...##..#.",
+             "..#.##.....",
+             ".#.#.#....#",
+             ".#........#",
+             "#.##...#...",
+             "#...##....#",
+             ".#..#...#.#"].join("\n")
+    );
+    let field = Field::from_reader(input).unwrap();
+    let slopes = [
+        (1, 1),
+        (3, 1),
+        (5, 1),
+        (7, 1),
+        (1, 2),
+    ];
+    let nums_trees: Vec<u64> = slopes.iter()
+        .map(|(right, down)| count_trees(&field, *right, *down))
+        .collect();
+    assert_eq!(nums_trees, vec![2, 7, 3, 4, 2]);
+    assert_eq!(nums_trees.iter().copied().product::<u64>(), 336);
+}
+
+#[test]
+fn test_count_trees_parallel() {
+    // a big, deterministic field with an odd width so the wraparound gets exercised
+    let mut seed: u64 = 12345;
+    let field: Field = (0..10_007)
+        .map(|_| Row {
+            columns: (0..31)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    if (seed >> 33) & 3 == 0 { Coordinate::Tree } else { Coordinate::Open }
+                })
+                .collect()
+        })
+        .collect();
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2), (2, 3), (11, 7)];
+    let sequential: Vec<u64> = slopes.iter()
+        .map(|(right, down)| count_trees(&field, *right, *down))
+        .collect();
+    assert_eq!(count_trees_parallel(&field, &slopes).unwrap(), sequential);
+
+    assert_eq!(count_trees_parallel(&Field::from_str("").unwrap(), &slopes).unwrap(), vec![0; slopes.len()]);
+    assert!(count_trees_parallel(&field, &[(1, 1), (3, 0)]).is_err());
+}
\ No newline at end of file
@@ -1,13 +1,14 @@
 #[macro_use] extern crate lazy_static;
 
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::{error::Error, str::FromStr};
-use std::fs::File;
-use std::io::{Read, BufRead, BufReader};
+use std::io::Read;
 
+use advent_common::{open_input, read_batches_with};
 use clap::{App, Arg};
 
-mod validated_passport;
+pub mod validated_passport;
 use validated_passport::ValidatedPassport;
 
 type AppResult<T> = Result<T, Box<dyn Error>>;
@@ -23,7 +24,7 @@ fn main() -> AppResult<()> {
             .help("Validate the passport fields (for part 2)"))
         .get_matches();
     
-    let f = File::open(matches.value_of("input").unwrap())?;
+    let f = open_input(matches.value_of("input").unwrap())?;
     let batch_lines = read_batch(f)?;
 
     // we don't care about the passports themselves - just that they're valid.
@@ -64,10 +65,38 @@ impl FromStr for Passport {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_tokens(tokenize(s)?)
+    }
+}
+
+impl Passport {
+    /// Like `from_str`, but a field that appears more than once is an error instead of the last one winning
+    pub fn from_str_strict(s: &str) -> AppResult<Self> {
+        Self::from_tokens(tokenize_strict(s)?)
+    }
+    /// Compares every field except `country_id`, which North Pole credentials don't have
+    pub fn eq_ignoring_country(&self, other: &Passport) -> bool {
+        self.birth_year == other.birth_year
+            && self.issue_year == other.issue_year
+            && self.expiration_year == other.expiration_year
+            && self.height == other.height
+            && self.hair_color == other.hair_color
+            && self.eye_color == other.eye_color
+            && self.passport_id == other.passport_id
+    }
+    /// A copy that's safe to share: the passport ID is zeroed out and the country ID is dropped. Everything else is
+    /// left alone.
+    pub fn anonymize(&self) -> Passport {
+        Passport {
+            passport_id: "000000000".to_string(),
+            country_id: None,
+            ..self.clone()
+        }
+    }
+    fn from_tokens(tokens: Vec<(&str, &str)>) -> AppResult<Self> {
         // we start with an empty builder. As we walk the fields included, we fill it out.
         // After we've walked all the fields, we ensure that all required fields are included.
         let mut builder = PassportBuilder::default();
-        let tokens: Vec<(&str, &str)> = tokenize(s)?;
 
         for (field, value) in tokens {
             let field = PassportFields::from_str(field)?;
@@ -105,6 +134,32 @@ pub struct PassportBuilder {
 }
 
 impl PassportBuilder {
+    /// The fields that have been filled in so far, in declaration order
+    pub fn present_fields(&self) -> Vec<PassportFields> {
+        PassportFields::all().iter()
+            .copied()
+            .filter(|&field| self.is_set(field))
+            .collect()
+    }
+    /// The required fields that haven't been filled in yet. Country ID is optional, so it's never missing.
+    pub fn missing_fields(&self) -> Vec<PassportFields> {
+        PassportFields::all().iter()
+            .copied()
+            .filter(|&field| field != PassportFields::CountryId && !self.is_set(field))
+            .collect()
+    }
+    fn is_set(&self, field: PassportFields) -> bool {
+        match field {
+            PassportFields::BirthYear => self.birth_year.is_some(),
+            PassportFields::IssueYear => self.issue_year.is_some(),
+            PassportFields::ExpirationYear => self.expiration_year.is_some(),
+            PassportFields::Height => self.height.is_some(),
+            PassportFields::HairColor => self.hair_color.is_some(),
+            PassportFields::EyeColor => self.eye_color.is_some(),
+            PassportFields::PassportId => self.passport_id.is_some(),
+            PassportFields::CountryId => self.country_id.is_some(),
+        }
+    }
     /// Build a Passport from this object. If a required field is missing, then an error will be returned.
     pub fn build(self) -> AppResult<Passport> {
         match self {
@@ -142,6 +197,35 @@ pub enum PassportFields {
     CountryId,
 }
 
+impl PassportFields {
+    /// The three-letter code for this field, as it appears in a passport record
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            Self::BirthYear => "byr",
+            Self::IssueYear => "iyr",
+            Self::ExpirationYear => "eyr",
+            Self::Height => "hgt",
+            Self::HairColor => "hcl",
+            Self::EyeColor => "ecl",
+            Self::PassportId => "pid",
+            Self::CountryId => "cid",
+        }
+    }
+    /// Every field, in declaration order
+    pub fn all() -> [PassportFields; 8] {
+        [
+            Self::BirthYear,
+            Self::IssueYear,
+            Self::ExpirationYear,
+            Self::Height,
+            Self::HairColor,
+            Self::EyeColor,
+            Self::PassportId,
+            Self::CountryId,
+        ]
+    }
+}
+
 impl FromStr for PassportFields {
     type Err = Box<dyn Error>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -161,24 +245,54 @@ impl FromStr for PassportFields {
 }
 
 pub fn read_batch(reader: impl Read) -> AppResult<Vec<String>> {
-    let mut output: Vec<String> = Vec::new();
-    let mut current_line = String::new();
-    for line in BufReader::new(reader).lines() {
-        let line: String = line?;
-        if line.is_empty() {
-            output.push(current_line);
-            current_line = String::new();
-        } else {
-            if ! current_line.is_empty() {
-                current_line.push(' ');
+    read_batch_with(reader, str::is_empty)
+}
+
+/**
+ * Like `read_batch`, but records are separated by any line that `is_separator` picks out instead of blank lines
+ */
+pub fn read_batch_with(reader: impl Read, is_separator: impl Fn(&str) -> bool) -> AppResult<Vec<String>> {
+    // each record can be spread over several lines. Join them back up into one line per record.
+    let output = read_batches_with(reader, is_separator)?
+        .into_iter()
+        .map(|batch| batch.join(" "))
+        .collect();
+    Ok(output)
+}
+
+/**
+ * Summary of a batch of passport records
+ */
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BatchReport {
+    /// number of records with all required fields present
+    pub present_count: usize,
+    /// number of records that also pass field validation
+    pub valid_count: usize,
+    /// indices (into the batch) of records that failed either check
+    pub invalid_indices: Vec<usize>,
+}
+
+/**
+ * Checks each record in a batch, counting the ones with required fields present and the ones that fully validate.
+ */
+pub fn report_batch(lines: &[String]) -> BatchReport {
+    let mut report = BatchReport::default();
+    for (idx, line) in lines.iter().enumerate() {
+        let passport = match Passport::from_str(line) {
+            Ok(p) => p,
+            Err(_) => {
+                report.invalid_indices.push(idx);
+                continue;
             }
-            current_line.push_str(&line);
+        };
+        report.present_count += 1;
+        match ValidatedPassport::try_from(passport) {
+            Ok(_) => report.valid_count += 1,
+            Err(_) => report.invalid_indices.push(idx),
         }
     }
-    if ! current_line.is_empty() {
-        output.push(current_line);
-    }
-    Ok(output)
+    report
 }
 
 /**
@@ -188,7 +302,7 @@ pub fn read_batch(reader: impl Read) -> AppResult<Vec<String>> {
  * assert_eq!(tokenize("ecl:gry pid:1234"), vec![("ecl", "gry"), ("pid", "1234")])
  * ```
  */
-pub fn tokenize<'a>(line: &'a str) -> AppResult<Vec<(&'a str, &'a str)>> {
+pub fn tokenize(line: &str) -> AppResult<Vec<(&str, &str)>> {
     let mut output = Vec::new();
     // first, iterate over the whitespace-delimited items
     for part in line.split_whitespace() {
@@ -203,6 +317,20 @@ pub fn tokenize<'a>(line: &'a str) -> AppResult<Vec<(&'a str, &'a str)>> {
     Ok(output)
 }
 
+/**
+ * Like `tokenize`, but errors if the same key shows up more than once
+ */
+pub fn tokenize_strict(line: &str) -> AppResult<Vec<(&str, &str)>> {
+    let tokens = tokenize(line)?;
+    let mut seen = HashSet::new();
+    for (key, _) in tokens.iter() {
+        if !seen.insert(*key) {
+            return Err(format!("duplicate field `{}`", key).into());
+        }
+    }
+    Ok(tokens)
+}
+
 #[test]
 fn test_part_1() {
     use std::io::Cursor;
@@ -247,6 +375,50 @@ fn test_read_batch() {
     assert_eq!(num_valid, 2);
 }
 
+#[test]
+fn test_read_batch_with() {
+    use std::io::Cursor;
+    let data = [
+        "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd",
+        "byr:1937 iyr:2017 cid:147 hgt:183cm",
+        "---",
+        "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884",
+        "hcl:#cfa07d byr:1929",
+    ].join("\n");
+    assert_eq!(read_batch_with(Cursor::new(&data), |line| line == "---").unwrap(), vec![
+        "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm",
+        "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884 hcl:#cfa07d byr:1929",
+    ]);
+    // with the default separator it's all one record
+    assert_eq!(read_batch(Cursor::new(&data)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_report_batch() {
+    use std::io::Cursor;
+    let data = vec![
+        "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980",
+        "hcl:#623a2f",
+        "",
+        "eyr:1972 cid:100",
+        "hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926",
+        "",
+        "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884",
+        "hcl:#cfa07d byr:1929",
+        "",
+        "iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+        "",
+        "hcl:dab227 iyr:2012",
+        "ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277",
+    ].join("\n");
+    let batch_lines = read_batch(Cursor::new(data)).unwrap();
+    assert_eq!(report_batch(&batch_lines), BatchReport {
+        present_count: 4,
+        valid_count: 2,
+        invalid_indices: vec![1, 2, 4],
+    });
+}
+
 #[test]
 fn test_tokenizer() {
     assert_eq!(tokenize("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd").unwrap(), vec![
@@ -254,6 +426,19 @@ fn test_tokenizer() {
     ]);
 }
 
+#[test]
+fn test_tokenizer_strict() {
+    assert_eq!(tokenize_strict("ecl:gry pid:860033327").unwrap(), tokenize("ecl:gry pid:860033327").unwrap());
+
+    let err = tokenize_strict("byr:1999 ecl:gry byr:2000").unwrap_err();
+    assert!(err.to_string().contains("`byr`"));
+
+    let line = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm byr:2000";
+    assert_eq!(Passport::from_str(line).unwrap().birth_year, "2000");
+    Passport::from_str_strict(line).unwrap_err();
+    assert_eq!(Passport::from_str_strict(&line[..line.len() - " byr:2000".len()]).unwrap().birth_year, "1937");
+}
+
 #[test]
 fn test_fields_from_str() {
     assert_eq!(PassportFields::from_str("byr").unwrap(), PassportFields::BirthYear);
@@ -268,6 +453,58 @@ fn test_fields_from_str() {
     assert!(PassportFields::from_str("lol").is_err())
 }
 
+#[test]
+fn test_passport_eq_ignoring_country() {
+    let with_country = Passport::from_str("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm").unwrap();
+    let without_country = Passport::from_str("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 hgt:183cm").unwrap();
+    assert!(with_country.eq_ignoring_country(&without_country));
+    assert!(without_country.eq_ignoring_country(&with_country));
+    assert_ne!(with_country, without_country);
+
+    let other_height = Passport::from_str("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 hgt:184cm").unwrap();
+    assert!(!other_height.eq_ignoring_country(&without_country));
+}
+
+#[test]
+fn test_passport_anonymize() {
+    let passport = Passport::from_str("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm").unwrap();
+    let anonymized = passport.anonymize();
+    assert_eq!(anonymized.passport_id, "000000000");
+    assert_eq!(anonymized.country_id, None);
+    assert_eq!(anonymized.height, passport.height);
+    assert_eq!(anonymized.birth_year, passport.birth_year);
+    // still a perfectly good passport
+    let validated = ValidatedPassport::try_from(anonymized).unwrap();
+    assert_eq!(validated.passport_id.0, "000000000");
+}
+
+#[test]
+fn test_builder_present_fields() {
+    let mut builder = PassportBuilder::default();
+    assert!(builder.present_fields().is_empty());
+    assert_eq!(builder.missing_fields().len(), 7);
+
+    builder.birth_year = Some("1937".to_string());
+    builder.height = Some("183cm".to_string());
+    builder.eye_color = Some("gry".to_string());
+    builder.country_id = Some("147".to_string());
+    assert_eq!(builder.present_fields(), vec![
+        PassportFields::BirthYear, PassportFields::Height, PassportFields::EyeColor, PassportFields::CountryId,
+    ]);
+    assert_eq!(builder.missing_fields(), vec![
+        PassportFields::IssueYear, PassportFields::ExpirationYear, PassportFields::HairColor, PassportFields::PassportId,
+    ]);
+}
+
+#[test]
+fn test_fields_as_key() {
+    for field in PassportFields::all().iter() {
+        assert_eq!(PassportFields::from_str(field.as_key()).unwrap(), *field);
+    }
+    assert_eq!(PassportFields::BirthYear.as_key(), "byr");
+    assert_eq!(PassportFields::CountryId.as_key(), "cid");
+}
+
 #[test]
 fn test_passport_from_str() {
     assert_eq!(Passport::from_str("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm").unwrap(), Passport {
@@ -1,5 +1,6 @@
 use std::convert::TryFrom;
 use std::error::Error;
+use std::num::ParseIntError;
 use std::str::FromStr;
 
 use regex::Regex;
@@ -40,17 +41,36 @@ impl TryFrom<Passport> for ValidatedPassport {
 }
 
 
+/**
+ * A year field that's only valid between `MIN` and `MAX`, inclusive.
+ */
+pub trait BoundedYear {
+    const MIN: u32;
+    const MAX: u32;
+}
+
+/// Parses a year, checking it against `Y`'s bounds
+fn parse_year<Y: BoundedYear>(s: &str) -> Result<u32, Box<dyn Error>> {
+    let num: u32 = s.parse()?;
+    if !(Y::MIN..=Y::MAX).contains(&num) {
+        return Err(format!("number out of range: `{}`", num).into());
+    }
+    Ok(num)
+}
+
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BirthYear(pub u32);
 
+impl BoundedYear for BirthYear {
+    const MIN: u32 = 1920;
+    const MAX: u32 = 2002;
+}
+
 impl FromStr for BirthYear {
     type Err = Box<dyn Error>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let num: u32 = s.parse()?;
-        if num < 1920 || num > 2002 {
-            return Err(format!("number out of range: `{}`", num).into());
-        }
-        Ok(BirthYear(num))
+        parse_year::<Self>(s).map(BirthYear)
     }
 }
 
@@ -58,14 +78,15 @@ impl FromStr for BirthYear {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct IssueYear(pub u32);
 
+impl BoundedYear for IssueYear {
+    const MIN: u32 = 2010;
+    const MAX: u32 = 2020;
+}
+
 impl FromStr for IssueYear {
     type Err = Box<dyn Error>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let num: u32 = s.parse()?;
-        if num < 2010 || num > 2020 {
-            return Err(format!("number out of range: `{}`", num).into());
-        }
-        Ok(IssueYear(num))
+        parse_year::<Self>(s).map(IssueYear)
     }
 }
 
@@ -73,14 +94,15 @@ impl FromStr for IssueYear {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ExpirationYear(pub u32);
 
+impl BoundedYear for ExpirationYear {
+    const MIN: u32 = 2020;
+    const MAX: u32 = 2030;
+}
+
 impl FromStr for ExpirationYear {
     type Err = Box<dyn Error>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let num: u32 = s.parse()?;
-        if num < 2020 || num > 2030 {
-            return Err(format!("number out of range: `{}`", num).into());
-        }
-        Ok(ExpirationYear(num))
+        parse_year::<Self>(s).map(ExpirationYear)
     }
 }
 
@@ -109,8 +131,8 @@ impl FromStr for Height {
 
         let num: u32 = num.parse()?;
         match unit {
-            "cm" if num >= 150 && num <= 193 => Ok(Height::Centimeters(num)),
-            "in" if num >= 59 && num <= 76 => Ok(Height::Inches(num)),
+            "cm" if (150..=193).contains(&num) => Ok(Height::Centimeters(num)),
+            "in" if (59..=76).contains(&num) => Ok(Height::Inches(num)),
             "cm" | "in" => Err(format!("invalid measurement for unit `{}`: `{}`", unit, num).into()),
             bad_unit => Err(format!("invalid unit specified: `{}`", bad_unit).into())
         }
@@ -133,7 +155,7 @@ impl FromStr for HairColor {
         };
         let color = match cap.name("hex") {
             Some(c) => c.as_str(),
-            None => return Err(format!("failed to capture color").into()),
+            None => return Err("failed to capture color".into()),
         };
         Ok(HairColor(color.to_string()))
     }
@@ -171,6 +193,18 @@ impl FromStr for EyeColor {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PassportId(pub String);
 
+impl PassportId {
+    /**
+     * The ID as a number. The string form keeps any leading zeros (`000000001` is still 9 characters long), but they're
+     * lost here (`000000001` is just 1), so two IDs are only the same if their strings match.
+     *
+     * Fails if the ID isn't all digits, which can only happen for one built directly instead of through `from_str`.
+     */
+    pub fn as_number(&self) -> Result<u32, ParseIntError> {
+        self.0.parse()
+    }
+}
+
 impl FromStr for PassportId {
     type Err = Box<dyn Error>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -194,6 +228,26 @@ fn test_birth_year_valid() {
     BirthYear::from_str("2003").unwrap_err();
 }
 
+#[test]
+fn test_year_bounds() {
+    assert_eq!(BirthYear::from_str("1920").unwrap(), BirthYear(1920));
+    BirthYear::from_str("1919").unwrap_err();
+    BirthYear::from_str("2003").unwrap_err();
+
+    assert_eq!(IssueYear::from_str("2010").unwrap(), IssueYear(2010));
+    assert_eq!(IssueYear::from_str("2020").unwrap(), IssueYear(2020));
+    IssueYear::from_str("2009").unwrap_err();
+    IssueYear::from_str("2021").unwrap_err();
+
+    assert_eq!(ExpirationYear::from_str("2020").unwrap(), ExpirationYear(2020));
+    assert_eq!(ExpirationYear::from_str("2030").unwrap(), ExpirationYear(2030));
+    ExpirationYear::from_str("2019").unwrap_err();
+    ExpirationYear::from_str("2031").unwrap_err();
+
+    assert_eq!(IssueYear::from_str("2021").unwrap_err().to_string(), "number out of range: `2021`");
+    IssueYear::from_str("twenty").unwrap_err();
+}
+
 #[test]
 fn test_height_valid() {
     assert_eq!(Height::from_str("60in").unwrap(), Height::Inches(60));
@@ -229,6 +283,15 @@ fn test_passport_id_valid() {
     PassportId::from_str("0123456789").unwrap_err();
 }
 
+#[test]
+fn test_passport_id_as_number() {
+    let id = PassportId::from_str("000000001").unwrap();
+    assert_eq!(id.0.len(), 9);
+    assert_eq!(id.as_number(), Ok(1));
+    assert_eq!(PassportId::from_str("999999999").unwrap().as_number(), Ok(999_999_999));
+    assert!(PassportId("abc".to_string()).as_number().is_err());
+}
+
 #[test]
 fn test_validated_passport_invalid() {
     use super::read_batch;
@@ -261,7 +324,7 @@ fn test_validated_passport_invalid() {
 fn test_validated_passport_valid() {
     use super::read_batch;
     use std::io::Cursor;
-    let data = vec![
+    let data = [
         "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980",
         "hcl:#623a2f",
         "",
@@ -277,9 +340,9 @@ fn test_validated_passport_valid() {
     ].join("\n");
     let batch_lines = read_batch(Cursor::new(data)).unwrap();
 
-    let num_valid = batch_lines.into_iter()
+    let validated: Vec<ValidatedPassport> = batch_lines.into_iter()
         .flat_map(|line| Passport::from_str(&line).ok())
         .map(|p| ValidatedPassport::try_from(p).unwrap())
-        .count();
-    assert_eq!(num_valid, 4);
+        .collect();
+    assert_eq!(validated.len(), 4);
 }
\ No newline at end of file
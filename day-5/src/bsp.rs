@@ -0,0 +1,38 @@
+use std::error::Error;
+
+/**
+ * Decodes a binary space partition. Each `upper` character keeps the upper half of the remaining space (a 1 bit), and
+ * each `lower` character keeps the lower half (a 0 bit). Any other character is an error.
+ *
+ * ```
+ * # use day_5::bsp;
+ * assert_eq!(bsp::decode("FBFBBFF", 'B', 'F').unwrap(), 44);
+ * ```
+ */
+pub fn decode(spec: &str, upper: char, lower: char) -> Result<u32, Box<dyn Error>> {
+    if spec.chars().count() > 32 {
+        return Err(format!("partition too long to decode: `{}`", spec).into());
+    }
+    let mut value = 0;
+    for (pos, ch) in spec.chars().enumerate() {
+        let bit = match ch {
+            c if c == upper => 1,
+            c if c == lower => 0,
+            c => return Err(format!("invalid character '{}' at position {} in partition `{}`", c, pos, spec).into()),
+        };
+        value = (value << 1) | bit;
+    }
+    Ok(value)
+}
+
+#[test]
+fn test_decode() {
+    assert_eq!(decode("FBFBBFF", 'B', 'F').unwrap(), 44);
+    assert_eq!(decode("RLR", 'R', 'L').unwrap(), 5);
+    assert_eq!(decode("", 'R', 'L').unwrap(), 0);
+    assert_eq!(decode("UUUUUUUUUUUUUUUUUUUUUUUUUUUUUUUU", 'U', 'D').unwrap(), u32::MAX);
+
+    let err = decode("RLB", 'R', 'L').unwrap_err();
+    assert!(err.to_string().contains("'B' at position 2"));
+    decode("UUUUUUUUUUUUUUUUUUUUUUUUUUUUUUUUU", 'U', 'D').unwrap_err();
+}
\ No newline at end of file
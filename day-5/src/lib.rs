@@ -0,0 +1,469 @@
+/*!
+ * Decoding boarding passes and finding the empty seats on the plane.
+ */
+use std::error::Error;
+use std::io::{Read, BufRead, BufReader};
+
+use advent_common::{first_gap, Puzzle};
+
+pub mod bsp;
+
+
+/**
+ * Returns the highest seat ID
+ */
+pub fn part_1(input: impl Read) -> Result<u32, Box<dyn Error>> {
+    let m = SeatMap::from_reader(input)?
+        .max_id()
+        .unwrap_or_default();
+    
+    Ok(m)
+}
+
+
+/**
+ * Returns the seat ID that is missing from the middle of the list
+ */
+pub fn part_2(input: impl Read) -> Result<u32, Box<dyn Error>> {
+    // our seat is the empty one with both neighbors taken, so any wider gaps are skipped
+    SeatMap::from_reader(input)?
+        .find_seat()
+        .ok_or_else(|| "no empty seat between two occupied seats".into())
+}
+
+
+/**
+ * Day 5 as a `Puzzle`. The input is the map of occupied seats.
+ */
+pub struct BinaryBoarding;
+
+impl Puzzle for BinaryBoarding {
+    type Input = SeatMap;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Box<dyn Error>> {
+        SeatMap::from_reader(input)
+    }
+    fn part1(input: &Self::Input) -> String {
+        input.max_id().unwrap_or_default().to_string()
+    }
+    fn part2(input: &Self::Input) -> String {
+        match input.find_seat() {
+            Some(id) => id.to_string(),
+            None => "no empty seat found".to_string(),
+        }
+    }
+}
+
+
+/**
+ * Returns every seat ID that is missing between the lowest and highest seat IDs present
+ */
+pub fn find_missing_seats(input: impl Read) -> Result<Vec<u32>, Box<dyn Error>> {
+    // first get the list of seat IDs
+    let mut seats: Vec<u32> = seat_ids(input)?;
+    
+    // now put them in order
+    seats.sort();
+
+    // now we window the data, and collect every ID that falls between (a, b) when (a + 1 != b)
+    let missing: Vec<u32> = seats.windows(2)
+        .filter(|&window| window[0] + 1 < window[1])
+        .flat_map(|window| (window[0] + 1)..window[1])
+        .collect();
+
+    Ok(missing)
+}
+
+
+/// number of seat IDs available on the plane (128 rows of 8 seats)
+const NUM_SEATS: usize = 128 * 8;
+
+/**
+ * Occupancy map of every seat on the plane, indexed by seat ID
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatMap {
+    occupied: Vec<bool>,
+}
+
+impl SeatMap {
+    /// Construct a seat map from a reader. Each line is a seating specifier for an occupied seat.
+    pub fn from_reader(r: impl Read) -> Result<Self, Box<dyn Error>> {
+        let mut occupied = vec![false; NUM_SEATS];
+        for id in seat_ids(r)? {
+            occupied[id as usize] = true;
+        }
+        Ok(Self { occupied })
+    }
+    /// Returns true if the seat with ID `id` is taken
+    pub fn is_occupied(&self, id: u32) -> bool {
+        self.occupied.get(id as usize).copied().unwrap_or(false)
+    }
+    /// Returns the lowest occupied seat ID
+    pub fn min_id(&self) -> Option<u32> {
+        self.occupied.iter().position(|&o| o).map(|id| id as u32)
+    }
+    /// Returns the highest occupied seat ID
+    pub fn max_id(&self) -> Option<u32> {
+        self.occupied.iter().rposition(|&o| o).map(|id| id as u32)
+    }
+    /// Returns every occupied seat ID, lowest first
+    pub fn occupied_ids(&self) -> Vec<u32> {
+        self.occupied.iter()
+            .enumerate()
+            .filter(|(_, &o)| o)
+            .map(|(id, _)| id as u32)
+            .collect()
+    }
+    /// Returns the first empty seat ID whose neighbors, `id - 1` and `id + 1`, are both occupied.
+    /// Runs of empty seats, like the ones that don't exist at the front and back of the plane, are skipped.
+    pub fn find_seat(&self) -> Option<u32> {
+        let ids = self.occupied_ids();
+        let mut rest = &ids[..];
+        while let Some(gap) = first_gap(rest) {
+            // `gap - 1` is occupied, since the gap starts right after it
+            if self.is_occupied(gap + 1) {
+                return Some(gap);
+            }
+            // skip the rest of this run of empty seats
+            let next = rest.iter().position(|&id| id > gap)?;
+            rest = &rest[next..];
+        }
+        None
+    }
+}
+
+
+/**
+ * Draws the plane, one row of 8 seats per line from front to back. Occupied seats are `#` and empty ones are `.`.
+ * IDs that don't fit on the plane are ignored.
+ */
+pub fn render_plane(ids: &[u32]) -> String {
+    let mut occupied = vec![false; NUM_SEATS];
+    for id in ids.iter().filter(|&&id| (id as usize) < NUM_SEATS) {
+        occupied[*id as usize] = true;
+    }
+    let mut output = String::with_capacity(NUM_SEATS + NUM_SEATS / 8);
+    for row in occupied.chunks(8) {
+        output.extend(row.iter().map(|&o| if o { '#' } else { '.' }));
+        output.push('\n');
+    }
+    output
+}
+
+
+/**
+ * Reads one seating specifier per line and converts each into its seat ID
+ */
+pub fn seat_ids(input: impl Read) -> Result<Vec<u32>, Box<dyn Error>> {
+    let ids = decode_seats(input)?
+        .into_iter()
+        .map(|(row, seat)| seat_id(row, seat))
+        .collect();
+    Ok(ids)
+}
+
+
+/**
+ * Reads one seating specifier per line and decodes each into its row and column
+ */
+pub fn decode_seats(input: impl Read) -> Result<Vec<(u8, u8)>, Box<dyn Error>> {
+    let mut seats = Vec::new();
+    for line in BufReader::new(input).lines() {
+        seats.push(seat_to_rows(&line?)?);
+    }
+    Ok(seats)
+}
+
+
+/**
+ * Takes a seating specifier and converts it to row and column
+ *
+ * ```
+ * # use day_5::seat_to_rows;
+ * assert_eq!(seat_to_rows("FBFBBFFRLR").unwrap(), (44, 5));
+ * ```
+ */
+pub fn seat_to_rows(seatcode: &str) -> Result<(u8, u8), Box<dyn Error>> {
+    if seatcode.len() != 10 {
+        return Err(format!("wrong length for seat specifier: `{}`", seatcode).into());
+    }
+    if let Some((pos, ch)) = seatcode.chars().enumerate().find(|(_, ch)| !"FBLR".contains(*ch)) {
+        return Err(format!("invalid character '{}' at position {} in seat specifier: `{}`", ch, pos, seatcode).into());
+    }
+    let row = bsp::decode(&seatcode[..7], 'B', 'F')? as u8;
+    let seat = bsp::decode(&seatcode[7..], 'R', 'L')? as u8;
+    Ok((row, seat))
+}
+
+
+/**
+ * Converts a seating specifier straight to its seat ID. The ID is just the specifier read as a 10-bit number.
+ *
+ * ```
+ * # use day_5::code_to_id;
+ * assert_eq!(code_to_id("FBFBBFFRLR").unwrap(), 357);
+ * ```
+ */
+pub fn code_to_id(code: &str) -> Result<u32, Box<dyn Error>> {
+    if code.len() != 10 {
+        return Err(format!("wrong length for seat specifier: `{}`", code).into());
+    }
+    code.chars().enumerate().try_fold(0, |id, (pos, ch)| {
+        // the first 7 characters pick the row, and the last 3 pick the seat
+        let bit = match (pos < 7, ch) {
+            (true, 'F') | (false, 'L') => 0,
+            (true, 'B') | (false, 'R') => 1,
+            _ => return Err(format!("invalid character '{}' at position {} in seat specifier: `{}`", ch, pos, code).into()),
+        };
+        Ok((id << 1) | bit)
+    })
+}
+
+
+/**
+ * Converts a row and seat back into a seating specifier. The inverse of `seat_to_rows`.
+ *
+ * ```
+ * # use day_5::encode_seat;
+ * assert_eq!(encode_seat(44, 5).unwrap(), "FBFBBFFRLR");
+ * ```
+ */
+pub fn encode_seat(row: u8, seat: u8) -> Result<String, Box<dyn Error>> {
+    if row >= 128 {
+        return Err(format!("row out of range: `{}`", row).into());
+    }
+    if seat >= 8 {
+        return Err(format!("seat out of range: `{}`", seat).into());
+    }
+    let row_code = (0..7).rev().map(|bit| if (row >> bit) & 1 == 1 { 'B' } else { 'F' });
+    let seat_code = (0..3).rev().map(|bit| if (seat >> bit) & 1 == 1 { 'R' } else { 'L' });
+    Ok(row_code.chain(seat_code).collect())
+}
+
+
+/**
+ * Every seating specifier on the plane, in seat ID order from 0 to 1023
+ */
+pub fn all_codes() -> impl Iterator<Item=String> {
+    // every row and seat is in range, so encoding can't fail
+    (0..1024u32).map(|id| encode_seat((id / 8) as u8, (id % 8) as u8).unwrap())
+}
+
+
+/**
+ * Folds a binary-partition code of any length into a number. Characters in `high_chars` select the upper half (a 1 bit),
+ * anything else selects the lower half.
+ *
+ * ```
+ * # use day_5::decode_partition;
+ * assert_eq!(decode_partition("BFFFBBF", &['B']).unwrap(), 70);
+ * ```
+ */
+pub fn decode_partition(code: &str, high_chars: &[char]) -> Result<u32, Box<dyn Error>> {
+    let bits: String = code.chars()
+        .map(|ch| if high_chars.contains(&ch) { '1' } else { '0' })
+        .collect();
+    bsp::decode(&bits, '1', '0')
+        .map_err(|_| format!("partition code too long to decode: `{}`", code).into())
+}
+
+
+/**
+ * convert row/seat to a seat ID
+ *
+ * ```
+ * assert_eq!(day_5::seat_id(44, 5), 357);
+ * ```
+ */
+pub fn seat_id(row: u8, seat: u8) -> u32 {
+    (row as u32) * 8 + (seat as u32)
+}
+
+
+#[test]
+fn test_seat_to_rows() {
+    assert_eq!(seat_to_rows("FBFBBFFRLR").unwrap(), (44, 5));
+    assert_eq!(seat_to_rows("BFFFBBFRRR").unwrap(), (70, 7));
+    assert_eq!(seat_to_rows("FFFBBBFRRR").unwrap(), (14, 7));
+    assert_eq!(seat_to_rows("BBFFBBFRLL").unwrap(), (102, 4));
+}
+
+
+#[test]
+fn test_seat_to_rows_invalid() {
+    let err = seat_to_rows("FBFBBFFRLX").unwrap_err();
+    assert!(err.to_string().contains("'X' at position 9"));
+    seat_to_rows("FBFBBFFRL").unwrap_err();
+    // row and column letters can't be swapped around
+    seat_to_rows("FBFBBFFRLB").unwrap_err();
+    seat_to_rows("FBFBBFRRLR").unwrap_err();
+}
+
+
+#[test]
+fn test_code_to_id() {
+    for code in ["FBFBBFFRLR", "BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL"].iter() {
+        let (row, seat) = seat_to_rows(code).unwrap();
+        assert_eq!(code_to_id(code).unwrap(), seat_id(row, seat));
+    }
+    assert_eq!(code_to_id("BFFFBBFRRR").unwrap(), 567);
+
+    let err = code_to_id("FBFBBFFRLB").unwrap_err();
+    assert!(err.to_string().contains("'B' at position 9"));
+    code_to_id("FBFBBFFRL").unwrap_err();
+}
+
+
+#[test]
+fn test_encode_seat() {
+    for code in ["FBFBBFFRLR", "BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL"].iter() {
+        let (row, seat) = seat_to_rows(code).unwrap();
+        assert_eq!(&encode_seat(row, seat).unwrap(), code);
+    }
+    encode_seat(128, 0).unwrap_err();
+    encode_seat(0, 8).unwrap_err();
+}
+
+
+#[test]
+fn test_all_codes() {
+    let codes: Vec<String> = all_codes().collect();
+    assert_eq!(codes.len(), 1024);
+    assert_eq!(codes[0], "FFFFFFFLLL");
+    assert_eq!(code_to_id(&codes[0]).unwrap(), 0);
+    assert_eq!(code_to_id(&codes[1023]).unwrap(), 1023);
+    assert!(codes.iter().enumerate().all(|(id, code)| code_to_id(code).unwrap() == id as u32));
+}
+
+
+#[test]
+fn test_decode_partition() {
+    assert_eq!(decode_partition("BFFFBBFR", &['B', 'R']).unwrap(), 0b1000_1101);
+    assert_eq!(decode_partition("UDDU", &['U']).unwrap(), 0b1001);
+    assert_eq!(decode_partition("", &['B']).unwrap(), 0);
+
+    assert_eq!(decode_partition("FBFBBFFRLR", &['B', 'R']).unwrap(), 44 * 8 + 5);
+    assert_eq!(decode_partition("BFFFBBFRRR", &['B', 'R']).unwrap(), 70 * 8 + 7);
+    assert_eq!(decode_partition("FFFBBBFRRR", &['B', 'R']).unwrap(), 14 * 8 + 7);
+    assert_eq!(decode_partition("BBFFBBFRLL", &['B', 'R']).unwrap(), 102 * 8 + 4);
+    decode_partition(&"B".repeat(33), &['B']).unwrap_err();
+}
+
+
+#[test]
+fn test_seat_id() {
+    assert_eq!(seat_id(70, 7), 567);
+    assert_eq!(seat_id(14, 7), 119);
+    assert_eq!(seat_id(102, 4), 820);
+}
+
+
+#[test]
+fn test_find_missing_seats() {
+    use std::io::Cursor;
+    // row 1, seats 0, 1, 3, 4, 6 (IDs 8, 9, 11, 12, 14)
+    let codes = [
+        "FFFFFFBLLL",
+        "FFFFFFBLLR",
+        "FFFFFFBLRR",
+        "FFFFFFBRLL",
+        "FFFFFFBRRL",
+    ].join("\n");
+    assert_eq!(find_missing_seats(Cursor::new(codes)).unwrap(), vec![10, 13]);
+}
+
+
+#[test]
+fn test_seat_ids() {
+    use std::io::Cursor;
+    let text = include_str!("../input.txt");
+    let ids = seat_ids(Cursor::new(text)).unwrap();
+    assert_eq!(ids.len(), text.lines().count());
+    assert_eq!(ids.iter().max().copied(), Some(896));
+}
+
+
+#[test]
+fn test_decode_seats() {
+    use std::io::Cursor;
+    let codes = "FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL";
+    assert_eq!(decode_seats(Cursor::new(codes)).unwrap(), vec![(44, 5), (70, 7), (14, 7), (102, 4)]);
+    decode_seats(Cursor::new("FBFBBFFRLR\nFBFBBFFRLX")).unwrap_err();
+}
+
+
+#[test]
+fn test_render_plane() {
+    let plane = render_plane(&[0, 9, 15, seat_id(127, 7), 5000]);
+    let lines: Vec<&str> = plane.lines().collect();
+    assert_eq!(lines.len(), 128);
+    assert!(lines.iter().all(|line| line.len() == 8));
+    assert_eq!(lines[0], "#.......");
+    assert_eq!(lines[1], ".#.....#");
+    assert_eq!(lines[2], "........");
+    assert_eq!(lines[127], ".......#");
+    assert_eq!(plane.matches('#').count(), 4);
+}
+
+
+#[test]
+fn test_seat_map() {
+    use std::io::Cursor;
+    let text = include_str!("../input.txt");
+    let map = SeatMap::from_reader(Cursor::new(text)).unwrap();
+    assert_eq!(map.min_id(), Some(53));
+    assert_eq!(map.max_id(), Some(896));
+    assert_eq!(map.occupied_ids().len(), text.lines().count());
+    assert_eq!(map.find_seat(), Some(659));
+    assert!(map.is_occupied(658));
+    assert!(!map.is_occupied(659));
+    assert!(map.is_occupied(660));
+    assert!(!map.is_occupied(2000));
+}
+
+
+#[test]
+fn test_find_seat() {
+    use std::io::Cursor;
+    // IDs 3, 6, 7, 8, 10, 11, 12. Seat 4 is the first gap, but 5 is empty too, so it isn't ours
+    let codes: Vec<String> = [3, 6, 7, 8, 10, 11, 12].iter()
+        .map(|id| encode_seat(id / 8, id % 8).unwrap())
+        .collect();
+    let map = SeatMap::from_reader(Cursor::new(codes.join("\n"))).unwrap();
+    assert_eq!(map.occupied_ids(), vec![3, 6, 7, 8, 10, 11, 12]);
+    assert_eq!(first_gap(&map.occupied_ids()), Some(4));
+    assert_eq!(map.find_seat(), Some(9));
+    assert_eq!(part_2(Cursor::new(codes.join("\n"))).unwrap(), 9);
+
+    // no seat has both neighbors
+    let map = SeatMap::from_reader(Cursor::new(codes[..4].join("\n"))).unwrap();
+    assert_eq!(map.find_seat(), None);
+}
+
+
+#[test]
+fn test_part_1() {
+    use std::io::Cursor;
+    let text = include_str!("../input.txt");
+    let max = part_1(Cursor::new(text)).unwrap();
+    assert_eq!(max, 896)
+}
+
+
+#[test]
+fn test_part_2() {
+    use std::io::Cursor;
+    let text = include_str!("../input.txt");
+    assert_eq!(part_2(Cursor::new(text)).unwrap(), 659);
+}
+
+
+#[test]
+fn test_puzzle() {
+    use std::io::Cursor;
+    let text = include_str!("../input.txt");
+    let map = BinaryBoarding::parse(Cursor::new(text)).unwrap();
+    assert_eq!(BinaryBoarding::part1(&map), "896");
+    assert_eq!(BinaryBoarding::part2(&map), "659");
+}
\ No newline at end of file
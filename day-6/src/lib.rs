@@ -0,0 +1,307 @@
+/*!
+ * Tallying up the yes answers on each family's customs declaration form.
+ */
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::io::{Read, BufRead};
+use std::iter::FromIterator;
+
+use advent_common::{read_batches, Puzzle};
+
+pub fn part_1(input: impl Read) -> Result<usize, Box<dyn Error>> {
+    let data: Vec<Vec<String>> = parse_answers(input)?;
+    Ok(sum_answers(&data, CombineMode::AnyoneAnsweredYes))
+}
+
+pub fn part_2(input: impl Read) -> Result<usize, Box<dyn Error>> {
+    let data: Vec<Vec<String>> = parse_answers(input)?;
+    Ok(sum_answers(&data, CombineMode::EveryoneAnsweredYes))
+}
+
+/**
+ * Day 6 as a `Puzzle`. The input is the list of families' answers.
+ */
+pub struct CustomCustoms;
+
+impl Puzzle for CustomCustoms {
+    type Input = Vec<Vec<String>>;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Box<dyn Error>> {
+        parse_answers(input)
+    }
+    fn part1(input: &Self::Input) -> String {
+        sum_answers(input, CombineMode::AnyoneAnsweredYes).to_string()
+    }
+    fn part2(input: &Self::Input) -> String {
+        sum_answers(input, CombineMode::EveryoneAnsweredYes).to_string()
+    }
+}
+
+/**
+ * Adds up the number of questions answered yes by each family, combining answers within a family by `combine`
+ */
+pub fn sum_answers(families: &[Vec<String>], combine: CombineMode) -> usize {
+    families.iter()
+        .map(|family| get_answers(family, combine).len())
+        .sum()
+}
+
+/**
+ * Every question that anybody in any family answered yes to
+ */
+pub fn all_questions(input: impl Read) -> Result<HashSet<char>, Box<dyn Error>> {
+    let data: Vec<Vec<String>> = parse_answers(input)?;
+    Ok(data.iter()
+        .flat_map(|family| family.iter())
+        .flat_map(|person_answer| person_answer.chars())
+        .collect())
+}
+
+/**
+ * Tallies, across every family, how many (family, question) pairs had each number of yes answers.
+ * The keys are the number of people who answered yes, and the values are how many times that happened.
+ */
+pub fn answer_count_histogram(input: impl Read) -> Result<BTreeMap<usize, usize>, Box<dyn Error>> {
+    let data: Vec<Vec<String>> = parse_answers(input)?;
+    let mut histogram = BTreeMap::new();
+    for count in data.iter().flat_map(|family| count_answers(family).into_values()) {
+        *histogram.entry(count).or_insert(0) += 1;
+    }
+    Ok(histogram)
+}
+
+/**
+ * Computes the part 1 (anyone answered yes) and part 2 (everyone answered yes) sums with a single parse of the input
+ */
+pub fn both_parts(input: impl Read) -> Result<(usize, usize), Box<dyn Error>> {
+    let data: Vec<Vec<String>> = parse_answers(input)?;
+    Ok(data.iter()
+        .map(|family| (get_answers(family, CombineMode::AnyoneAnsweredYes).len(),
+                       get_answers(family, CombineMode::EveryoneAnsweredYes).len()))
+        .fold((0, 0), |(anyone, everyone), (a, e)| (anyone + a, everyone + e)))
+}
+
+/**
+ * Take the input data and parse it into a list of strings, one vec for each family
+ */
+pub fn parse_answers(input: impl Read) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    // Content between empty lines is grouped into a vector.
+    Ok(read_batches(input)?)
+}
+
+/**
+ * Like `parse_answers`, but errors if any answer contains something other than a lowercase letter
+ */
+pub fn parse_answers_checked(input: impl Read) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let grouped = parse_answers(input)?;
+    for (group_idx, family) in grouped.iter().enumerate() {
+        let bad_char = family.iter()
+            .flat_map(|person_answer| person_answer.chars())
+            .find(|c| !c.is_ascii_lowercase());
+        if let Some(c) = bad_char {
+            return Err(format!("invalid answer '{}' in group {}: {:?}", c, group_idx, family).into());
+        }
+    }
+    Ok(grouped)
+}
+
+/**
+ * Lazily group the input, yielding one family's answers at a time as each blank-line-delimited record is completed.
+ * Iteration stops at the end of the input, or at the first read error.
+ */
+pub fn groups(input: impl BufRead) -> impl Iterator<Item=Vec<String>> {
+    let mut lines = input.lines();
+    std::iter::from_fn(move || {
+        let mut current = Vec::new();
+        for line in lines.by_ref() {
+            let line = line.ok()?;
+            if line.is_empty() {
+                return Some(current);
+            }
+            current.push(line);
+        }
+        // end of input. Only yield the last group if it has something in it
+        if current.is_empty() {
+            None
+        } else {
+            Some(current)
+        }
+    })
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CombineMode {
+    AnyoneAnsweredYes,
+    EveryoneAnsweredYes,
+    /// more than half of the family answered yes
+    MajorityAnsweredYes,
+    /// only one person in the family answered yes
+    ExactlyOne,
+}
+
+/**
+ * Take a list of strings, pull each string apart into chars, throw the chars into sets, and combine the sets based on the combine mode
+ */
+pub fn get_answers(family_answers: &[String], combine: CombineMode) -> HashSet<char> {
+    if let CombineMode::MajorityAnsweredYes | CombineMode::ExactlyOne = combine {
+        // sets can't tell us how many people answered, so tally them up and keep the counts we want
+        let family_size = family_answers.len();
+        return count_answers(family_answers).into_iter()
+            .filter(|&(_, count)| match combine {
+                CombineMode::ExactlyOne => count == 1,
+                _ => count * 2 > family_size,
+            })
+            .map(|(question, _)| question)
+            .collect();
+    }
+    let mut first = true;
+    family_answers.iter()
+        .map(|person_answer| HashSet::from_iter(person_answer.chars()))
+        // fold_first would work better, but it's not on stable rust yet
+        .fold(HashSet::new(), |answers, next_member_answer| {
+            if first {
+                first = false;
+                next_member_answer
+            } else {
+                match combine {
+                    CombineMode::AnyoneAnsweredYes => { answers.union(&next_member_answer).copied().collect() }
+                    CombineMode::EveryoneAnsweredYes => { answers.intersection(&next_member_answer).copied().collect() }
+                    CombineMode::MajorityAnsweredYes | CombineMode::ExactlyOne => unreachable!("these are handled by tallying answers"),
+                }
+            }
+        })
+}
+
+/**
+ * Tally, for each question, how many members of the family answered yes to it
+ */
+pub fn count_answers(family: &[String]) -> BTreeMap<char, usize> {
+    family.iter()
+        .map(|person_answer| person_answer.chars().collect::<HashSet<char>>())
+        .fold(BTreeMap::new(), |mut counts, answers| {
+            for question in answers {
+                *counts.entry(question).or_insert(0) += 1;
+            }
+            counts
+        })
+}
+
+#[test]
+fn test_get_answers() {
+    use std::iter::FromIterator;
+    let answers = vec!["abcx".to_string(), "abcy".to_string(), "abcz".to_string()];
+    assert_eq!(get_answers(&answers, CombineMode::AnyoneAnsweredYes),
+               HashSet::from_iter(vec!['a', 'b', 'c', 'x', 'y', 'z'].into_iter()));
+    assert_eq!(get_answers(&answers, CombineMode::EveryoneAnsweredYes),
+               HashSet::from_iter(vec!['a', 'b', 'c'].into_iter()));
+}
+
+#[test]
+fn test_get_answers_majority() {
+    use std::iter::FromIterator;
+    let answers = vec!["ab".to_string(), "ac".to_string(), "bd".to_string()];
+    assert_eq!(get_answers(&answers, CombineMode::MajorityAnsweredYes),
+               HashSet::from_iter(vec!['a', 'b'].into_iter()));
+
+    let answers = vec!["ab".to_string(), "cd".to_string()];
+    assert!(get_answers(&answers, CombineMode::MajorityAnsweredYes).is_empty());
+}
+
+#[test]
+fn test_get_answers_exactly_one() {
+    use std::iter::FromIterator;
+    let answers = vec!["abc".to_string(), "abd".to_string()];
+    assert_eq!(get_answers(&answers, CombineMode::ExactlyOne), HashSet::from_iter(vec!['c', 'd'].into_iter()));
+
+    // with only one person, everything they answered counts
+    let answers = vec!["ab".to_string()];
+    assert_eq!(get_answers(&answers, CombineMode::ExactlyOne), HashSet::from_iter(vec!['a', 'b'].into_iter()));
+}
+
+#[test]
+fn test_count_answers() {
+    let answers = vec!["ab".to_string(), "ac".to_string()];
+    assert_eq!(count_answers(&answers), vec![('a', 2), ('b', 1), ('c', 1)].into_iter().collect::<BTreeMap<_, _>>());
+}
+
+#[test]
+fn test_parse() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(parse_answers(Cursor::new(answers)).unwrap(), vec![
+        vec!["abc".to_string()],
+        vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        vec!["ab".to_string(), "ac".to_string()],
+        vec!["a".to_string(), "a".to_string(), "a".to_string(), "a".to_string()],
+        vec!["b".to_string()],
+    ]);
+}
+
+#[test]
+fn test_parse_checked() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc";
+    assert_eq!(parse_answers_checked(Cursor::new(answers)).unwrap(), parse_answers(Cursor::new(answers)).unwrap());
+
+    let answers = "abc\n\na\nB\nc";
+    let err = parse_answers_checked(Cursor::new(answers)).unwrap_err();
+    assert!(err.to_string().contains("'B' in group 1"));
+}
+
+#[test]
+fn test_groups() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(groups(Cursor::new(answers)).collect::<Vec<_>>(), parse_answers(Cursor::new(answers)).unwrap());
+}
+
+#[test]
+fn test_part_1() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(part_1(Cursor::new(answers)).unwrap(), 11);
+}
+
+#[test]
+fn test_part_2() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(part_2(Cursor::new(answers)).unwrap(), 6);
+}
+
+#[test]
+fn test_both_parts() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(both_parts(Cursor::new(answers)).unwrap(), (11, 6));
+}
+
+#[test]
+fn test_all_questions() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(all_questions(Cursor::new(answers)).unwrap(), HashSet::from_iter(vec!['a', 'b', 'c'].into_iter()));
+
+    let answers = "ab\n\nxy\nz";
+    assert_eq!(all_questions(Cursor::new(answers)).unwrap().len(), 5);
+    assert!(all_questions(Cursor::new("")).unwrap().is_empty());
+}
+
+#[test]
+fn test_answer_count_histogram() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    assert_eq!(answer_count_histogram(Cursor::new(answers)).unwrap(),
+               vec![(1, 9), (2, 1), (4, 1)].into_iter().collect::<BTreeMap<_, _>>());
+    assert!(answer_count_histogram(Cursor::new("")).unwrap().is_empty());
+}
+
+#[test]
+fn test_puzzle() {
+    use std::io::Cursor;
+    let answers = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    let input = CustomCustoms::parse(Cursor::new(answers)).unwrap();
+    assert_eq!(CustomCustoms::part1(&input), "11");
+    assert_eq!(CustomCustoms::part2(&input), "6");
+}
\ No newline at end of file
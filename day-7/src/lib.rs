@@ -0,0 +1,547 @@
+/*!
+ * Rules for which colors of bags hold which other bags, and how many of them.
+ */
+#[macro_use] extern crate lazy_static;
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::error::Error;
+use std::io::{Read, BufRead, BufReader};
+
+use regex::Regex;
+
+/**
+ * How many colors can eventually contain at least one shiny gold bag?
+ */
+pub fn part_1(input: impl Read) -> Result<usize, Box<dyn Error>> {
+    let bags = Bags::from_reader(input)?;
+    let found: BTreeSet<String> = bags.contains("shiny gold").collect();
+    Ok(found.len())
+}
+
+pub fn part_2(input: impl Read, bag_type: &str) -> Result<u32, Box<dyn Error>> {
+    let bags = Bags::from_reader(input)?;
+    match bags.count_contents(bag_type) {
+        Some(count) => Ok(count),
+        None => Err("no bags found".into()),
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Bags {
+    rules: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl Bags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Reads one rule per line, adding them all
+    pub fn from_reader(input: impl Read) -> Result<Self, Box<dyn Error>> {
+        let mut bags = Self::new();
+        for line in BufReader::new(input).lines() {
+            bags.insert_by_line(&line?)?;
+        }
+        Ok(bags)
+    }
+    /// Parses a rule and adds it. If there's already a rule for the same outer bag, it's replaced.
+    pub fn insert_by_line(&mut self, line: &str) -> Result<(), Box<dyn Error>> {
+        lazy_static! {
+            // colors are one or more words. Matching lazily stops the color at the first `bag`/`bags`
+            static ref SPLITTER: Regex = Regex::new(r"^\s*(?P<bigbag>\w+(?:\s+\w+)*?)\s+bags\s+contain\s+(?P<rest>.*)\.").unwrap();
+            static ref CONTAINS: Regex = Regex::new(r"(?P<num>\d+)\s+(?P<bag>[[:alpha:]]+(?:\s+[[:alpha:]]+)*?)\s+bag(s)?\b,?").unwrap();
+        }
+        let split = match SPLITTER.captures(line) {
+            Some(cap) => cap,
+            None => return Err(format!("failed to parse bag rule: `{}`", line).into()),
+        };
+        let bigbag = &split["bigbag"];
+        let rest = &split["rest"];
+        let mut bag_contents = BTreeMap::new();
+        for cap in CONTAINS.captures_iter(rest) {
+            let num: u32 = match cap["num"].parse() {
+                Ok(n) => n,
+                Err(e) => return Err(format!("bad bag count `{}` in rule `{}`: {}", &cap["num"], line, e).into()),
+            };
+            bag_contents.insert(cap["bag"].to_string(), num);
+        }
+        self.rules.insert(bigbag.to_string(), bag_contents);
+        Ok(())
+    }
+    /// Adds all of `other`'s rules. Where both have a rule for the same outer bag, `other`'s rule wins, just like
+    /// inserting the same bag twice.
+    pub fn merge(&mut self, other: Bags) {
+        self.rules.extend(other.rules);
+    }
+    /// Removes the rule for `bag`, returning what it held if the rule existed
+    pub fn remove(&mut self, bag: &str) -> Option<BTreeMap<String, u32>> {
+        self.rules.remove(bag)
+    }
+    /// Returns true if there's a rule for `bag`
+    pub fn contains_key(&self, bag: &str) -> bool {
+        self.rules.contains_key(bag)
+    }
+    /// Returns the bags that hold `inner` directly, without going through another bag. Sorted by color.
+    pub fn direct_containers(&self, inner: &str) -> Vec<String> {
+        self.rules.iter()
+            .filter(|&(_, value)| value.contains_key(inner))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+    pub fn contains(&self, smaller_bag: &str) -> impl Iterator<Item=String> {
+        let mut found: BTreeSet<String> = self.direct_containers(smaller_bag).into_iter().collect();
+        let mut current_len = found.len();
+        let mut last_len = 0;
+
+        while current_len != last_len {
+            last_len = current_len;
+            let next_found: Vec<_> = found.iter().flat_map(|s| self.direct_containers(s)).collect();
+            found.extend(next_found);
+            current_len = found.len();
+        }
+        
+        found.into_iter()
+    }
+    /// Like `contains`, but in breadth-first order: bags directly holding `inner` come first, then the bags holding
+    /// those, and so on. Within a layer, bags are sorted.
+    pub fn contains_bfs(&self, inner: &str) -> Vec<String> {
+        let mut found: Vec<String> = Vec::new();
+        let mut seen: BTreeSet<String> = BTreeSet::new();
+        let mut queue: VecDeque<String> = VecDeque::new();
+        queue.push_back(inner.to_string());
+        while let Some(current) = queue.pop_front() {
+            for outer in self.direct_containers(&current) {
+                if outer != inner && seen.insert(outer.clone()) {
+                    found.push(outer.clone());
+                    queue.push_back(outer);
+                }
+            }
+        }
+        found
+    }
+    /// Returns every bag that can eventually hold at least one of `inner_bags`
+    pub fn contains_any(&self, inner_bags: &[&str]) -> BTreeSet<String> {
+        inner_bags.iter()
+            .flat_map(|bag| self.contains(bag))
+            .collect()
+    }
+    pub fn contents(&self, key: &str) -> Option<impl Iterator<Item=(String, u32)>> {
+        self.rules.get(key).map(|hm| hm.clone().into_iter())
+    }
+    pub fn contents_recursive(&self, key: &str) -> Option<impl Iterator<Item=(String, u32)>> {
+        let mut visiting = BTreeSet::new();
+        self.contents_recursive_guarded(key, &mut visiting).map(|v| v.into_iter())
+    }
+    /// Returns every distinct bag that `key` holds, directly or transitively
+    pub fn descendants(&self, key: &str) -> BTreeSet<String> {
+        let mut found: BTreeSet<String> = BTreeSet::new();
+        let mut to_visit: Vec<&str> = vec![key];
+        while let Some(bag) = to_visit.pop() {
+            for inner in self.rules.get(bag).into_iter().flat_map(|contents| contents.keys()) {
+                if found.insert(inner.clone()) {
+                    to_visit.push(inner);
+                }
+            }
+        }
+        found
+    }
+    /// How many distinct colors of bag `key` holds, directly or transitively. Unlike `count_contents`, each color is
+    /// only counted once, however many of it there are.
+    pub fn reachable_count(&self, key: &str) -> usize {
+        self.descendants(key).len()
+    }
+    /// Counts every bag nested within `key`, at any depth. Returns None if there's no rule for `key`.
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use day_7::Bags;
+    /// let rules = "shiny gold bags contain 2 dark red bags.\n\
+    ///              dark red bags contain 3 faded blue bags.\n\
+    ///              faded blue bags contain no other bags.";
+    /// let bags = Bags::from_reader(Cursor::new(rules)).unwrap();
+    /// assert_eq!(bags.count_contents("shiny gold"), Some(8));
+    /// ```
+    pub fn count_contents(&self, key: &str) -> Option<u32> {
+        self.contents_recursive(key).map(|it| it.map(|(_, num)| num).sum())
+    }
+    /// Like `count_contents`, but counts the outer `key` bag as well
+    pub fn count_contents_inclusive(&self, key: &str) -> Option<u32> {
+        self.count_contents(key).map(|count| count + 1)
+    }
+    /// Walks the contents of `key`, skipping any bag already being walked further up the stack so cyclic rules terminate.
+    fn contents_recursive_guarded(&self, key: &str, visiting: &mut BTreeSet<String>) -> Option<Vec<(String, u32)>> {
+        let mut output: Vec<(String, u32)> = Vec::new();
+        let found = self.contents(key)?;
+        visiting.insert(key.to_string());
+        for (key, num) in found {
+            output.push((key.clone(), num));
+            if visiting.contains(&key) {
+                continue;
+            }
+            if let Some(it) = self.contents_recursive_guarded(&key, visiting) {
+                let it_mult = it.into_iter().map(|(key, oldnum)| (key, num*oldnum));
+                output.extend(it_mult);
+            }
+        }
+        visiting.remove(key);
+        Some(output)
+    }
+    /// Returns how many bags deep the nesting can go inside `key`: 0 if it holds nothing, 1 if it only holds empty bags,
+    /// and so on. A cycle is only followed until it gets back to a bag already on the path.
+    pub fn max_depth(&self, key: &str) -> usize {
+        let mut visiting = BTreeSet::new();
+        self.max_depth_guarded(key, &mut visiting)
+    }
+    fn max_depth_guarded(&self, key: &str, visiting: &mut BTreeSet<String>) -> usize {
+        let inner_bags = match self.rules.get(key) {
+            Some(contents) => contents,
+            None => return 0,
+        };
+        visiting.insert(key.to_string());
+        let depth = inner_bags.keys()
+            .map(|inner| if visiting.contains(inner) { 1 } else { 1 + self.max_depth_guarded(inner, visiting) })
+            .max()
+            .unwrap_or(0);
+        visiting.remove(key);
+        depth
+    }
+    /// Looks for a cycle in the rules. If one exists, returns the bags along it, starting and ending with the same bag.
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        let mut done = BTreeSet::new();
+        for key in self.rules.keys() {
+            let mut path = Vec::new();
+            if let Some(cycle) = self.find_cycle_from(key, &mut path, &mut done) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+    /// depth-first search for a cycle. `path` holds the bags currently being visited, `done` the bags known to be cycle-free.
+    fn find_cycle_from(&self, key: &str, path: &mut Vec<String>, done: &mut BTreeSet<String>) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|bag| bag == key) {
+            // we've come back around to a bag we're still visiting
+            let mut cycle = path[start..].to_vec();
+            cycle.push(key.to_string());
+            return Some(cycle);
+        }
+        if done.contains(key) {
+            return None;
+        }
+        path.push(key.to_string());
+        if let Some(contents) = self.rules.get(key) {
+            for inner in contents.keys() {
+                if let Some(cycle) = self.find_cycle_from(inner, path, done) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+        done.insert(key.to_string());
+        None
+    }
+    /// Finds the shortest chain of bags from the outer bag `from` down to the inner bag `to`, including both ends.
+    pub fn shortest_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        // breadth-first search, remembering which bag we reached each bag from so the path can be rebuilt
+        let mut came_from: BTreeMap<String, String> = BTreeMap::new();
+        let mut queue: VecDeque<String> = VecDeque::new();
+        queue.push_back(from.to_string());
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![current];
+                while let Some(prev) = came_from.get(path.last().unwrap()) {
+                    path.push(prev.clone());
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for inner in self.rules.get(&current).into_iter().flat_map(|contents| contents.keys()) {
+                if inner != from && !came_from.contains_key(inner) {
+                    came_from.insert(inner.clone(), current.clone());
+                    queue.push_back(inner.clone());
+                }
+            }
+        }
+        None
+    }
+    /// Renders the rules as a Graphviz digraph. Each edge points from the outer bag to a bag it holds, labeled with the count.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bags {\n");
+        for (outer, contents) in self.rules.iter() {
+            if contents.is_empty() {
+                dot.push_str(&format!("    \"{}\";\n", outer));
+            }
+            for (inner, num) in contents.iter() {
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\"];\n", outer, inner, num));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The example rules from the puzzle description
+#[cfg(test)]
+fn sample_bags() -> Bags {
+    let rules = [
+        "light red bags contain 1 bright white bag, 2 muted yellow bags.",
+        "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
+        "bright white bags contain 1 shiny gold bag.",
+        "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
+        "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
+        "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
+        "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
+        "faded blue bags contain no other bags.",
+        "dotted black bags contain no other bags.",
+    ];
+    let mut b = Bags::new();
+    for each in rules.iter() {
+        b.insert_by_line(each).unwrap();
+    }
+    b
+}
+
+#[test]
+fn test_bag_insert() {
+    let mut b = Bags::new();
+    b.insert_by_line("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+    assert_eq!(b.rules.get("light red").unwrap(), &vec![("bright white".to_string(), 1u32), ("muted yellow".to_string(), 2u32)].into_iter().collect::<BTreeMap<_, _>>());
+}
+
+
+#[test]
+fn test_bags_from_reader() {
+    use std::io::Cursor;
+    let rules = "light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+                 bright white bags contain 1 shiny gold bag.\n\
+                 faded blue bags contain no other bags.\n";
+    let b = Bags::from_reader(Cursor::new(rules)).unwrap();
+    assert_eq!(b.rules.len(), 3);
+    assert!(b.contains_key("light red"));
+    assert!(b.contains_key("faded blue"));
+
+    Bags::from_reader(Cursor::new("light red bags contain 1 bright white bag\n")).unwrap_err();
+}
+
+#[test]
+fn test_bag_override_and_remove() {
+    let mut b = Bags::new();
+    b.insert_by_line("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+    b.insert_by_line("bright white bags contain 1 shiny gold bag.").unwrap();
+    assert!(b.contains_key("light red"));
+
+    // inserting the same outer bag again replaces the old rule
+    b.insert_by_line("light red bags contain 3 shiny gold bags.").unwrap();
+    assert_eq!(b.contents("light red").unwrap().collect::<Vec<_>>(), vec![("shiny gold".to_string(), 3u32)]);
+
+    assert_eq!(b.remove("light red"), Some(vec![("shiny gold".to_string(), 3u32)].into_iter().collect()));
+    assert!(!b.contains_key("light red"));
+    assert!(b.contents("light red").is_none());
+    assert_eq!(b.contains("shiny gold").collect::<Vec<_>>(), vec!["bright white".to_string()]);
+    assert_eq!(b.remove("light red"), None);
+}
+
+
+#[test]
+fn test_bag_merge() {
+    let mut first = Bags::new();
+    first.insert_by_line("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+    first.insert_by_line("bright white bags contain 1 shiny gold bag.").unwrap();
+    let mut second = Bags::new();
+    second.insert_by_line("bright white bags contain 2 faded blue bags.").unwrap();
+    second.insert_by_line("faded blue bags contain no other bags.").unwrap();
+
+    first.merge(second);
+    assert_eq!(first.rules.len(), 3);
+    assert!(first.contains_key("light red"));
+    assert_eq!(first.contents("bright white").unwrap().collect::<Vec<_>>(), vec![("faded blue".to_string(), 2)]);
+}
+
+#[test]
+fn test_bag_insert_long_colors() {
+    let mut b = Bags::new();
+    b.insert_by_line("dark vibrant plum bags contain 2 pale light cyan bags, 1 shiny gold bag.").unwrap();
+    b.insert_by_line("pale light cyan bags contain no other bags.").unwrap();
+    assert_eq!(b.contents("dark vibrant plum").unwrap().collect::<Vec<_>>(),
+               vec![("pale light cyan".to_string(), 2u32), ("shiny gold".to_string(), 1u32)]);
+    assert_eq!(b.contents("pale light cyan").unwrap().count(), 0);
+    assert_eq!(b.contains("pale light cyan").collect::<Vec<_>>(), vec!["dark vibrant plum".to_string()]);
+}
+
+
+#[test]
+fn test_bag_insert_invalid() {
+    let mut b = Bags::new();
+    let err = b.insert_by_line("this is not a rule").unwrap_err();
+    assert!(err.to_string().contains("this is not a rule"));
+    assert_eq!(b, Bags::new());
+
+    assert!(part_2(std::io::Cursor::new("shiny gold bags contain 1 dark olive bag.\ngarbage"), "shiny gold").is_err());
+}
+
+
+#[test]
+fn test_contents() {
+    let mut b = Bags::new();
+    b.insert_by_line("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+
+    assert_eq!(b.contents("light red").unwrap().collect::<Vec<_>>(), vec![("bright white".to_string(), 1u32), ("muted yellow".to_string(), 2u32)]);
+}
+
+#[test]
+fn test_find_cycle() {
+    let mut b = Bags::new();
+    b.insert_by_line("light red bags contain 1 bright white bag.").unwrap();
+    b.insert_by_line("bright white bags contain 2 muted yellow bags.").unwrap();
+    b.insert_by_line("muted yellow bags contain no other bags.").unwrap();
+    assert!(b.find_cycle().is_none());
+
+    b.insert_by_line("muted yellow bags contain 3 light red bags.").unwrap();
+    assert_eq!(b.find_cycle().unwrap(), vec!["bright white", "muted yellow", "light red", "bright white"]);
+
+    // walking the contents of a cyclic rule set should still terminate
+    assert_eq!(b.contents_recursive("light red").unwrap().collect::<Vec<_>>(),
+               vec![("bright white".to_string(), 1), ("muted yellow".to_string(), 2), ("light red".to_string(), 6)]);
+}
+
+#[test]
+fn test_shortest_path() {
+    let b = sample_bags();
+    assert_eq!(b.shortest_path("light red", "shiny gold").unwrap(), vec!["light red", "bright white", "shiny gold"]);
+    assert_eq!(b.shortest_path("light red", "faded blue").unwrap(), vec!["light red", "muted yellow", "faded blue"]);
+    assert_eq!(b.shortest_path("shiny gold", "shiny gold").unwrap(), vec!["shiny gold"]);
+    assert!(b.shortest_path("shiny gold", "light red").is_none());
+}
+
+#[test]
+fn test_to_dot() {
+    let mut b = Bags::new();
+    b.insert_by_line("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+    b.insert_by_line("bright white bags contain 1 shiny gold bag.").unwrap();
+
+    let dot = b.to_dot();
+    assert!(dot.starts_with("digraph bags {\n"));
+    assert!(dot.contains("    \"light red\" -> \"bright white\" [label=\"1\"];\n"));
+    assert!(dot.contains("    \"light red\" -> \"muted yellow\" [label=\"2\"];\n"));
+    assert!(dot.contains("    \"bright white\" -> \"shiny gold\" [label=\"1\"];\n"));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn test_contents_recursive() {
+    let b = sample_bags();
+
+    /* bright white bags can hold:
+     * - shiny gold
+     *   - dark olive
+     *     - faded blue
+     *     - dotted black
+     *   - vibrant plum
+     *     - faded blue
+     *     - dotted black
+     */
+    assert_eq!(b.contents_recursive("bright white").unwrap().collect::<Vec<_>>(),
+               vec![("shiny gold".to_string(), 1),
+                    ("dark olive".to_string(), 1u32), ("dotted black".to_string(), 4), ("faded blue".to_string(), 3),
+                    ("vibrant plum".to_string(), 2), ("dotted black".to_string(), 12), ("faded blue".to_string(), 10)]);
+}
+
+#[test]
+fn test_count_contents() {
+    let b = sample_bags();
+    assert_eq!(b.count_contents("shiny gold"), Some(32));
+    assert_eq!(b.count_contents("faded blue"), Some(0));
+    assert_eq!(b.count_contents("plaid magenta"), None);
+}
+
+#[test]
+fn test_count_contents_inclusive() {
+    let b = sample_bags();
+    assert_eq!(b.count_contents_inclusive("shiny gold"), Some(33));
+    assert_eq!(b.count_contents_inclusive("faded blue"), Some(1));
+    assert_eq!(b.count_contents_inclusive("plaid magenta"), None);
+}
+
+#[test]
+fn test_contains_any() {
+    let mut b = sample_bags();
+    b.insert_by_line("pale cyan bags contain 1 dark olive bag.").unwrap();
+    b.insert_by_line("wavy red bags contain 1 vibrant plum bag.").unwrap();
+    let olive: BTreeSet<String> = b.contains("dark olive").collect();
+    let plum: BTreeSet<String> = b.contains("vibrant plum").collect();
+    let both = b.contains_any(&["dark olive", "vibrant plum"]);
+
+    assert!(olive.contains("pale cyan") && !plum.contains("pale cyan"));
+    assert!(plum.contains("wavy red") && !olive.contains("wavy red"));
+    assert_eq!(both, olive.union(&plum).cloned().collect());
+    assert_ne!(both, olive);
+    assert_ne!(both, plum);
+    assert!(b.contains_any(&[]).is_empty());
+}
+
+#[test]
+fn test_direct_containers() {
+    let b = sample_bags();
+    assert_eq!(b.direct_containers("shiny gold"), vec!["bright white", "muted yellow"]);
+    assert!(b.direct_containers("light red").is_empty());
+}
+
+#[test]
+fn test_max_depth() {
+    let mut b = sample_bags();
+    assert_eq!(b.max_depth("shiny gold"), 2);
+    assert_eq!(b.max_depth("light red"), 4);
+    assert_eq!(b.max_depth("faded blue"), 0);
+    assert_eq!(b.max_depth("plaid magenta"), 0);
+
+    // faded blue -> shiny gold -> dark olive -> faded blue is a loop
+    b.insert_by_line("faded blue bags contain 1 shiny gold bag.").unwrap();
+    assert_eq!(b.max_depth("shiny gold"), 3);
+}
+
+#[test]
+fn test_contains_bfs() {
+    let b = sample_bags();
+    // the direct containers come before the bags that hold them, even though they sort after
+    assert_eq!(b.contains_bfs("shiny gold"), vec!["bright white", "muted yellow", "dark orange", "light red"]);
+    let sorted: BTreeSet<String> = b.contains_bfs("shiny gold").into_iter().collect();
+    assert_eq!(sorted, b.contains("shiny gold").collect());
+    assert!(b.contains_bfs("light red").is_empty());
+}
+
+#[test]
+fn test_descendants() {
+    let b = sample_bags();
+    let expected: BTreeSet<String> = ["dark olive", "vibrant plum", "faded blue", "dotted black"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(b.descendants("shiny gold"), expected);
+    assert!(b.descendants("faded blue").is_empty());
+}
+
+#[test]
+fn test_part_2() {
+    use std::io::Cursor;
+    let rules = vec![
+        "light red bags contain 1 bright white bag, 2 muted yellow bags.",
+        "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
+        "bright white bags contain 1 shiny gold bag.",
+        "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
+        "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
+        "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
+        "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
+        "faded blue bags contain no other bags.",
+        "dotted black bags contain no other bags.",
+    ];
+    assert_eq!(part_2(Cursor::new(rules.join("\n")), "faded blue").unwrap(), 0);
+    assert_eq!(part_2(Cursor::new(rules.join("\n")), "dotted black").unwrap(), 0);
+    assert_eq!(part_2(Cursor::new(rules.join("\n")), "vibrant plum").unwrap(), 11);
+    assert_eq!(part_2(Cursor::new(rules.join("\n")), "dark olive").unwrap(), 7);
+    assert_eq!(part_2(Cursor::new(rules.join("\n")), "shiny gold").unwrap(), 32);
+}
+
+#[test]
+fn test_reachable_count() {
+    let b = sample_bags();
+    assert_eq!(b.reachable_count("shiny gold"), 4);
+    assert_eq!(b.count_contents("shiny gold"), Some(32));
+    assert_eq!(b.reachable_count("light red"), 7);
+    assert_eq!(b.reachable_count("faded blue"), 0);
+    assert_eq!(b.reachable_count("no such color"), 0);
+}
\ No newline at end of file
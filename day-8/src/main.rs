@@ -1,7 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::fmt;
 use std::io::{self, Read, BufRead, BufReader};
 use std::fs::File;
+use std::ops::Range;
 use std::str::FromStr;
 
 use clap::{App, Arg};
@@ -20,18 +22,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         .map(|i| Instruction::from_str(&i).unwrap())
         .collect();
     
-    let mut e = Emulator::new();
-    let mut seen: HashSet<usize> = HashSet::new();
-    while seen.insert(e.pc) {
-        e.execute(instructions[e.pc]);
-    }
-    println!("first loop at PC {pc}. Accumulator = {acc}", pc=e.pc, acc=e.accumulator);
+    let (pc, e) = run_until_loop(&instructions);
+    println!("first loop at PC {pc}. Accumulator = {acc}", pc=pc, acc=e.accumulator);
 
     match run_program(Emulator::new(), &instructions, 1, HashSet::new()) {
-        TerminationCriteria::Terminated(emulator) => {
+        TerminationCriteria::Terminated(emulator, mutated) => {
             println!("successfully terminated. Accumulator value = {}", emulator.accumulator);
+            if let Some(idx) = mutated {
+                println!("fixed by changing instruction {}.", idx);
+            }
         }
-        TerminationCriteria::InfiniteLoop => {
+        TerminationCriteria::InfiniteLoop(_) | TerminationCriteria::StepLimitExceeded(_) | TerminationCriteria::OutOfBounds(_) => {
             println!("oh noes no paths to termination found.");
         }
     }
@@ -39,6 +40,45 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 
+/**
+ * Runs the program without any mutation until an instruction is about to run a second time.
+ *
+ * Returns the PC of the repeated instruction, and the emulator state just before it would run again.
+ * If the program runs off the end instead, the returned PC is the one past the end.
+ */
+pub fn run_until_loop(instructions: &[Instruction]) -> (usize, Emulator) {
+    let mut e = Emulator::new();
+    let mut seen: HashSet<usize> = HashSet::new();
+    while seen.insert(e.pc) {
+        match instructions.get(e.pc) {
+            Some(i) => e.execute(*i),
+            None => break,
+        }
+    }
+    (e.pc, e)
+}
+
+/**
+ * Runs the program without any mutation and returns the period of its infinite loop: the number of instructions
+ * executed between first reaching the repeated PC and reaching it again.
+ *
+ * Returns `None` if the program halts, or jumps somewhere outside of it, instead of looping.
+ */
+pub fn loop_length(instructions: &[Instruction]) -> Option<usize> {
+    let mut e = Emulator::new();
+    // PC => how many instructions had run when we first got there
+    let mut first_seen: HashMap<usize, usize> = HashMap::new();
+    for steps in 0.. {
+        if let Some(first) = first_seen.insert(e.pc, steps) {
+            return Some(steps - first);
+        }
+        if e.step(instructions) != StepResult::Running {
+            return None;
+        }
+    }
+    unreachable!()
+}
+
 /**
  * Attempt to run the program from the current emulator state.
  * 
@@ -46,30 +86,42 @@ fn main() -> Result<(), Box<dyn Error>> {
  * NOP or JUMP can be mutated to JUMP or NOP, respectively, but only `instructions_that_can_change` times.
  * Infinite loop detection is done by pushing the current PC into a set. If that specific PC has already been seen, an infinite loop has occurred.
  */
-pub fn run_program(mut emulator: Emulator,
+pub fn run_program(emulator: Emulator,
                    instructions: &[Instruction],
                    instructions_that_can_change: i32,
-                   mut instructions_hit: HashSet<usize>) -> TerminationCriteria {
+                   instructions_hit: HashSet<usize>) -> TerminationCriteria {
+    run_program_in_range(emulator, instructions, 0..instructions.len(), instructions_that_can_change, instructions_hit)
+}
+
+/**
+ * Like `run_program`, but only instructions with an index in `range` may be mutated.
+ */
+pub fn run_program_in_range(mut emulator: Emulator,
+                            instructions: &[Instruction],
+                            range: Range<usize>,
+                            instructions_that_can_change: i32,
+                            mut instructions_hit: HashSet<usize>) -> TerminationCriteria {
     // loop until we encounter an infinite loop (detected by inserting PC into a set and checking to see if we've that PC value before)
     while instructions_hit.insert(emulator.pc) {
         // try to get the next instruction. If the PC has gone off the end, then the program has terminated.
         let next_instruction = match instructions.get(emulator.pc) {
-            None => return TerminationCriteria::Terminated(emulator), // WE DID IT, WE SAVED THE DAY!
+            None => return TerminationCriteria::Terminated(emulator, None), // WE DID IT, WE SAVED THE DAY!
             Some(i) => i,
         };
         // try to mutate the instruction JUMP{value} <=> NOP{value}; but only if we're still allowed to change instructions!
         // when a mutation is available, try to run the program to completion with that mutation.
         // If we're successful, then return with the end state of that emulator. Otherwise, continue executing from here.
         match next_instruction.try_mutate() {
-            Some(mutated) if instructions_that_can_change > 0 => {
+            Some(mutated) if instructions_that_can_change > 0 && range.contains(&emulator.pc) => {
                 // mutation is allowed! Try to run the program to completion with this mutation.
+                let mutated_pc = emulator.pc;
                 let mut alternate_reality = emulator.clone();
                 alternate_reality.execute(mutated); // have to execute the mutated instruction separately, since it's not part of the instruction slice
-                let terminated_how = run_program(alternate_reality, instructions, instructions_that_can_change - 1, instructions_hit.clone());
+                let terminated_how = run_program_in_range(alternate_reality, instructions, range.clone(), instructions_that_can_change - 1, instructions_hit.clone());
 
-                if let done @ TerminationCriteria::Terminated(_) = terminated_how {
-                    // program terminated successfully. Pass that up the stack.
-                    return done;
+                if let TerminationCriteria::Terminated(done, _) = terminated_how {
+                    // program terminated successfully. Pass that up the stack, noting the first instruction we changed.
+                    return TerminationCriteria::Terminated(done, Some(mutated_pc));
                 }
             },
             // mutation is not allowed. Pass-through.
@@ -79,13 +131,93 @@ pub fn run_program(mut emulator: Emulator,
         emulator.execute(*next_instruction);
     }
     // Infinite loop occurred. Bail.
-    TerminationCriteria::InfiniteLoop
+    TerminationCriteria::InfiniteLoop(instructions_hit)
+}
+
+/**
+ * Returns the index of every instruction that can be mutated (NOP <=> JUMP)
+ */
+pub fn mutable_indices(instructions: &[Instruction]) -> Vec<usize> {
+    instructions.iter()
+        .enumerate()
+        .filter(|(_, instruction)| instruction.try_mutate().is_some())
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/**
+ * Finds the single NOP <=> JUMP change that lets the program terminate, by running the whole program once per candidate.
+ *
+ * Returns the index of the changed instruction and the emulator state at termination.
+ */
+pub fn find_fix_linear(instructions: &[Instruction]) -> Option<(usize, Emulator)> {
+    let mut patched = instructions.to_vec();
+    let mut emulator = Emulator::new();
+    for (idx, instruction) in instructions.iter().enumerate() {
+        let mutated = match instruction.try_mutate() {
+            Some(m) => m,
+            None => continue,
+        };
+        patched[idx] = mutated;
+        emulator.reset();
+        let mut visited = vec![false; patched.len()];
+        let halted = loop {
+            // only fresh PCs make it through here, so this runs at most once per instruction
+            visited[emulator.pc] = true;
+            match emulator.step(&patched) {
+                StepResult::Running if visited[emulator.pc] => break false,
+                StepResult::Running => (),
+                StepResult::Halted => break true,
+                StepResult::OutOfBounds => break false,
+            }
+        };
+        if halted {
+            return Some((idx, emulator));
+        }
+        patched[idx] = *instruction;
+    }
+    None
+}
+
+/**
+ * Run the program from the current emulator state without any mutation, executing at most `max_steps` instructions.
+ *
+ * Termination and infinite loops are detected the same way as `run_program`; if neither happens before the step limit
+ * is reached, execution stops there. Instructions are run with `Emulator::step`, so a program that jumps outside of
+ * itself stops with `OutOfBounds` instead of panicking.
+ */
+pub fn run_program_bounded(mut emulator: Emulator, instructions: &[Instruction], max_steps: usize) -> TerminationCriteria {
+    let mut instructions_hit: HashSet<usize> = HashSet::new();
+    for _ in 0..max_steps {
+        if !instructions_hit.insert(emulator.pc) {
+            return TerminationCriteria::InfiniteLoop(instructions_hit);
+        }
+        match emulator.step(instructions) {
+            StepResult::Running => (),
+            StepResult::Halted => return TerminationCriteria::Terminated(emulator, None),
+            StepResult::OutOfBounds => return TerminationCriteria::OutOfBounds(emulator),
+        }
+    }
+    // out of steps. The program may still have been about to finish, so check that before giving up
+    match instructions.get(emulator.pc) {
+        None if emulator.pc == instructions.len() => TerminationCriteria::Terminated(emulator, None),
+        None => TerminationCriteria::OutOfBounds(emulator),
+        Some(_) if instructions_hit.contains(&emulator.pc) => TerminationCriteria::InfiniteLoop(instructions_hit),
+        Some(_) => TerminationCriteria::StepLimitExceeded(emulator),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TerminationCriteria {
-    InfiniteLoop,
-    Terminated(Emulator),
+    /// the program looped. Carries the set of PCs that were visited before the loop was detected
+    InfiniteLoop(HashSet<usize>),
+    /// the program ran off the end. Carries the final emulator state, and the index of the instruction that was mutated (if any)
+    Terminated(Emulator, Option<usize>),
+    /// the program was stopped after running too many instructions. Carries the emulator state when it was stopped
+    StepLimitExceeded(Emulator),
+    /// the program jumped before its start, or past the instruction after its last. Carries the emulator state when it
+    /// was stopped
+    OutOfBounds(Emulator),
 }
 
 fn get_input(input: impl Read) -> Result<Vec<String>, io::Error> {
@@ -94,11 +226,77 @@ fn get_input(input: impl Read) -> Result<Vec<String>, io::Error> {
     .collect()
 }
 
+/**
+ * Assembles a program that may use labels. A label is defined with `name:`, either on its own line or in front of an
+ * instruction, and refers to the index of the next instruction. `jmp name` is converted to the relative jump that lands there.
+ *
+ * ```text
+ * assert_eq!(assemble("top: acc +1\njmp top").unwrap(), vec![Instruction::Accumulate(1), Instruction::Jump(-1)]);
+ * ```
+ */
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, Box<dyn Error>> {
+    // first pass: find where each label points, and strip them off the instructions
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut lines: Vec<&str> = Vec::new();
+    for line in source.lines() {
+        let mut line = line.trim();
+        if let Some((label, rest)) = line.split_once(':') {
+            let label = label.trim();
+            if label.is_empty() || !label.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(format!("invalid label `{}`", label).into());
+            }
+            if labels.insert(label, lines.len()).is_some() {
+                return Err(format!("label `{}` defined more than once", label).into());
+            }
+            line = rest.trim();
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+
+    // second pass: parse the instructions, resolving symbolic jumps into relative ones
+    let mut output = Vec::new();
+    for (idx, line) in lines.into_iter().enumerate() {
+        let instruction = match line.split_once(char::is_whitespace) {
+            Some(("jmp", arg)) if arg.trim().parse::<i64>().is_err() => {
+                let label = arg.trim();
+                match labels.get(label) {
+                    Some(&target) => Instruction::Jump(target as i64 - idx as i64),
+                    None => return Err(format!("unknown label `{}`", label).into()),
+                }
+            },
+            _ => Instruction::from_str(line)?,
+        };
+        output.push(instruction);
+    }
+    Ok(output)
+}
+
+/**
+ * Prints a program back out in assembly form, one instruction per line, prefixed with its index.
+ *
+ * ```text
+ * assert_eq!(disassemble(&[Instruction::Accumulate(1), Instruction::Jump(-1)]), "000: acc +1\n001: jmp -1\n");
+ * ```
+ */
+pub fn disassemble(instructions: &[Instruction]) -> String {
+    let mut output = String::new();
+    for (idx, instruction) in instructions.iter().enumerate() {
+        output.push_str(&format!("{:03}: {}\n", idx, instruction));
+    }
+    output
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Instruction {
     NoOperation(i64),
     Accumulate(i64),
     Jump(i64),
+    /// multiply the accumulator by the operand
+    Multiply(i64),
+    /// push the accumulator onto the output log
+    Output,
 }
 
 impl Instruction {
@@ -107,6 +305,7 @@ impl Instruction {
             Self::NoOperation(value) => Some(Self::Jump(value)),
             Self::Accumulate(_) => None,
             Self::Jump(value) => Some(Self::NoOperation(value)),
+            Self::Multiply(_) | Self::Output => None,
         }
     }
 }
@@ -125,45 +324,241 @@ impl FromStr for Instruction {
             ("nop", Some(arg)) => Ok(Self::NoOperation(arg.parse()?)),
             ("acc", Some(arg)) => Ok(Self::Accumulate(arg.parse()?)),
             ("jmp", Some(arg)) => Ok(Self::Jump(arg.parse()?)),
-            ("nop", None) | ("acc", None) | ("jmp", None) => Err(format!("{op}: Expected argument, got none", op=op).into()),
+            ("mul", Some(arg)) => Ok(Self::Multiply(arg.parse()?)),
+            ("out", None) => Ok(Self::Output),
+            ("nop", None) | ("acc", None) | ("jmp", None) | ("mul", None) => Err(format!("{op}: Expected argument, got none", op=op).into()),
+            ("out", Some(arg)) => Err(format!("{op}: Expected no argument, got `{arg}`", op=op, arg=arg).into()),
             (bad_op, _) => Err(format!("Unexpected Operation `{op}`", op=bad_op).into()),
         }
     }
 }
 
+/**
+ * Writes the instruction in the same form `from_str` reads it, with an explicit sign on the operand
+ */
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoOperation(value) => write!(f, "nop {:+}", value),
+            Self::Accumulate(value) => write!(f, "acc {:+}", value),
+            Self::Jump(value) => write!(f, "jmp {:+}", value),
+            Self::Multiply(value) => write!(f, "mul {:+}", value),
+            Self::Output => write!(f, "out"),
+        }
+    }
+}
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Emulator {
     pub accumulator: i64,
     pub pc: usize,
+    /// values written by `out` instructions, in order
+    pub output: Vec<i64>,
+    /// if enabled, the accumulator value after each executed instruction
+    pub trace: Option<Vec<i64>>,
+    /// if enabled, the PC and operand of each executed `acc` instruction
+    acc_log: Option<Vec<(usize, i64)>>,
 }
 
+/// Outcome of stepping the emulator through a single instruction
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StepResult {
+    /// the PC still points into the program
+    Running,
+    /// the PC is just past the last instruction. The program has terminated normally.
+    Halted,
+    /// the PC would be before the start of the program, or beyond the instruction after the last one
+    OutOfBounds,
+}
+
+/// Reasons an instruction couldn't be executed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ExecError {
+    /// a jump would have moved the PC before the start of the program
+    PcUnderflow,
+    /// a jump would have moved the PC further than it can count
+    PcOverflow,
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PcUnderflow => write!(f, "Program Counter Underflow"),
+            Self::PcOverflow => write!(f, "Program Counter Overflow"),
+        }
+    }
+}
+
+impl Error for ExecError {}
+
 impl Emulator {
     pub fn new() -> Self { Self::default() }
+    /// Construct an emulator that records the accumulator after every instruction it executes
+    pub fn with_trace() -> Self {
+        Self {
+            trace: Some(Vec::new()),
+            ..Self::default()
+        }
+    }
+    /// Construct an emulator that records the PC and operand of every `acc` instruction it executes
+    pub fn with_acc_log() -> Self {
+        Self {
+            acc_log: Some(Vec::new()),
+            ..Self::default()
+        }
+    }
+    /// The `(pc, delta)` of each `acc` instruction executed so far. Always empty unless built with `with_acc_log`.
+    pub fn acc_log(&self) -> &[(usize, i64)] {
+        self.acc_log.as_deref().unwrap_or(&[])
+    }
+    /// Puts the emulator back to its starting state, keeping its allocations. Tracing and the `acc` log stay on if
+    /// they were enabled.
+    pub fn reset(&mut self) {
+        self.accumulator = 0;
+        self.pc = 0;
+        self.output.clear();
+        if let Some(trace) = self.trace.as_mut() {
+            trace.clear();
+        }
+        if let Some(acc_log) = self.acc_log.as_mut() {
+            acc_log.clear();
+        }
+    }
+    /// Executes the instruction at the current PC. Unlike `execute`, this never panics on a bad PC.
+    pub fn step(&mut self, instructions: &[Instruction]) -> StepResult {
+        let instruction = match instructions.get(self.pc) {
+            Some(i) => *i,
+            None if self.pc == instructions.len() => return StepResult::Halted,
+            None => return StepResult::OutOfBounds,
+        };
+        if self.execute_checked(instruction).is_err() {
+            // the emulator is left as-is rather than underflowing
+            return StepResult::OutOfBounds;
+        }
+        match self.pc {
+            pc if pc < instructions.len() => StepResult::Running,
+            pc if pc == instructions.len() => StepResult::Halted,
+            _ => StepResult::OutOfBounds,
+        }
+    }
+    /// Executes a single instruction, panicking if a jump would move the PC before the start of the program.
+    ///
+    /// Accumulator arithmetic wraps around on overflow (two's complement), the same in debug and release builds.
     pub fn execute(&mut self, instruction: Instruction) {
-        match instruction {
-            Instruction::NoOperation(_) => (),
+        if let Err(e) = self.execute_checked(instruction) {
+            panic!("{} on instruction {:?}", e, instruction);
+        }
+    }
+    /// Like `execute`, but a jump that would move the PC before the start of the program is an error instead of a
+    /// panic. The emulator is unchanged when that happens.
+    pub fn execute_checked(&mut self, instruction: Instruction) -> Result<(), ExecError> {
+        self.pc = match instruction {
+            Instruction::NoOperation(_) => self.pc + 1,
             Instruction::Accumulate(value) => {
-                self.accumulator += value;
+                if let Some(acc_log) = self.acc_log.as_mut() {
+                    acc_log.push((self.pc, value));
+                }
+                self.accumulator = self.accumulator.wrapping_add(value);
+                self.pc + 1
+            },
+            Instruction::Multiply(value) => {
+                self.accumulator = self.accumulator.wrapping_mul(value);
+                self.pc + 1
+            },
+            Instruction::Output => {
+                self.output.push(self.accumulator);
+                self.pc + 1
             },
             Instruction::Jump(rel) => {
-                let new_pc = self.pc as i64 + rel - 1;
+                // jumps set the PC directly, so `jmp +0` at the start of the program doesn't underflow
+                let new_pc = (self.pc as i64).checked_add(rel).ok_or(ExecError::PcOverflow)?;
                 if new_pc < 0 {
-                    panic!("Program Counter Underflow on instruction {:?}", instruction);
+                    return Err(ExecError::PcUnderflow);
                 }
-                self.pc = new_pc as usize;
+                new_pc as usize
             }
+        };
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(self.accumulator);
         }
-        self.pc += 1;
+        Ok(())
     }
 }
 
+/// The example program from the puzzle description
+#[cfg(test)]
+fn sample_program() -> Vec<Instruction> {
+    [
+        "nop +0", "acc +1", "jmp +4", "acc +3", "jmp -3", "acc -99", "acc +1", "jmp -4", "acc +6",
+    ].iter().map(|i| Instruction::from_str(i).unwrap()).collect()
+}
+
 #[test]
 fn test_instruction_from_str() {
     assert_eq!(Instruction::from_str("nop +0").unwrap(), Instruction::NoOperation(0));
     assert_eq!(Instruction::from_str("acc +1").unwrap(), Instruction::Accumulate(1));
     assert_eq!(Instruction::from_str("acc -99").unwrap(), Instruction::Accumulate(-99));
     assert_eq!(Instruction::from_str("jmp -4").unwrap(), Instruction::Jump(-4));
+    assert_eq!(Instruction::from_str("mul -3").unwrap(), Instruction::Multiply(-3));
+    assert_eq!(Instruction::from_str("out").unwrap(), Instruction::Output);
+
+    Instruction::from_str("mul").unwrap_err();
+    Instruction::from_str("out +1").unwrap_err();
+}
+
+#[test]
+fn test_instruction_display() {
+    assert_eq!(Instruction::NoOperation(0).to_string(), "nop +0");
+    assert_eq!(Instruction::Accumulate(-99).to_string(), "acc -99");
+    assert_eq!(Instruction::Jump(4).to_string(), "jmp +4");
+
+    let instrs = [
+        Instruction::NoOperation(0),
+        Instruction::Accumulate(-99),
+        Instruction::Jump(4),
+        Instruction::Multiply(-3),
+        Instruction::Output,
+    ];
+    for i in instrs.iter() {
+        assert_eq!(Instruction::from_str(&i.to_string()).unwrap(), *i);
+    }
+}
+
+#[test]
+fn test_disassemble() {
+    let instrs: Vec<Instruction> = [
+        "nop +0", "acc +1", "jmp +4", "acc +3", "jmp -3", "acc -99", "mul +2", "out", "jmp -4", "acc +6",
+    ].iter().map(|i| Instruction::from_str(i).unwrap()).collect();
+
+    let text = disassemble(&instrs);
+    assert!(text.starts_with("000: nop +0\n001: acc +1\n002: jmp +4\n"));
+
+    let reparsed: Vec<Instruction> = text.lines()
+        .map(|line| line.split_once(": ").unwrap().1)
+        .map(|i| Instruction::from_str(i).unwrap())
+        .collect();
+    assert_eq!(reparsed, instrs);
+    assert_eq!(disassemble(&[Instruction::Accumulate(1), Instruction::Jump(-1)]), "000: acc +1\n001: jmp -1\n");
+}
+
+#[test]
+fn test_emulator_mul_out() {
+    let instrs = [
+        Instruction::from_str("acc +3").unwrap(),
+        Instruction::from_str("out").unwrap(),
+        Instruction::from_str("mul +4").unwrap(),
+        Instruction::from_str("out").unwrap(),
+        Instruction::from_str("mul -2").unwrap(),
+        Instruction::from_str("out").unwrap(),
+    ];
+    let mut e = Emulator::new();
+    while let Some(i) = instrs.get(e.pc) {
+        e.execute(*i);
+    }
+    assert_eq!(e.accumulator, -24);
+    assert_eq!(e.output, vec![3, 12, -24]);
+    assert_eq!(instrs[2].try_mutate(), None);
+    assert_eq!(instrs[1].try_mutate(), None);
 }
 
 #[test]
@@ -195,4 +590,296 @@ fn test_emulator() {
     assert_eq!(e.pc, 4);
     e.execute(instrs[e.pc]); // jmp -3
     assert_eq!(e.pc, 1);
+}
+
+#[test]
+fn test_run_program_fix_index() {
+    let instrs = sample_program();
+
+    match run_program(Emulator::new(), &instrs, 1, HashSet::new()) {
+        TerminationCriteria::Terminated(e, mutated) => {
+            assert_eq!(e.accumulator, 8);
+            assert_eq!(mutated, Some(7));
+        }
+        other => panic!("expected program to terminate, got {:?}", other),
+    }
+
+    // a program that terminates on its own doesn't need a fix
+    let no_jumps = [Instruction::Accumulate(1), Instruction::Accumulate(2)];
+    match run_program(Emulator::new(), &no_jumps, 1, HashSet::new()) {
+        TerminationCriteria::Terminated(_, mutated) => assert_eq!(mutated, None),
+        other => panic!("expected program to terminate, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_run_program_in_range() {
+    let instrs = sample_program();
+
+    // the fix is changing the `jmp -4` at index 7
+    match run_program_in_range(Emulator::new(), &instrs, 0..7, 1, HashSet::new()) {
+        TerminationCriteria::InfiniteLoop(_) => (),
+        other => panic!("expected an infinite loop, got {:?}", other),
+    }
+    match run_program_in_range(Emulator::new(), &instrs, 7..9, 1, HashSet::new()) {
+        TerminationCriteria::Terminated(e, Some(7)) => assert_eq!(e.accumulator, 8),
+        other => panic!("expected program to terminate, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_run_program_infinite_loop() {
+    // changing the first `jmp` just lands on the second one, which jumps back into the loop
+    let instrs = [Instruction::Accumulate(1), Instruction::Jump(-1), Instruction::Jump(-1)];
+    match run_program(Emulator::new(), &instrs, 1, HashSet::new()) {
+        TerminationCriteria::InfiniteLoop(visited) => {
+            assert_eq!(visited, vec![0, 1].into_iter().collect::<HashSet<usize>>());
+        }
+        other => panic!("expected an infinite loop, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_emulator_step() {
+    let instrs = [Instruction::Accumulate(1), Instruction::Jump(1), Instruction::Accumulate(2)];
+    let mut e = Emulator::new();
+    assert_eq!(e.step(&instrs), StepResult::Running);
+    assert_eq!(e.step(&instrs), StepResult::Running);
+    assert_eq!(e.step(&instrs), StepResult::Halted);
+    assert_eq!(e.accumulator, 3);
+    // stepping a halted program doesn't do anything
+    assert_eq!(e.step(&instrs), StepResult::Halted);
+    assert_eq!(e.accumulator, 3);
+
+    let instrs = [Instruction::Accumulate(1), Instruction::Jump(5)];
+    let mut e = Emulator::new();
+    assert_eq!(e.step(&instrs), StepResult::Running);
+    assert_eq!(e.step(&instrs), StepResult::OutOfBounds);
+}
+
+#[test]
+fn test_emulator_step_underflow() {
+    let instrs = [Instruction::NoOperation(0), Instruction::Jump(-2)];
+    let mut e = Emulator::new();
+    assert_eq!(e.step(&instrs), StepResult::Running);
+    assert_eq!(e.step(&instrs), StepResult::OutOfBounds);
+    assert_eq!(e.pc, 1);
+}
+
+#[test]
+fn test_execute_checked() {
+    let mut e = Emulator::with_trace();
+    e.execute_checked(Instruction::Accumulate(2)).unwrap();
+    e.execute_checked(Instruction::Jump(3)).unwrap();
+    assert_eq!(e.pc, 4);
+
+    let before = e.clone();
+    assert_eq!(e.execute_checked(Instruction::Jump(-5)), Err(ExecError::PcUnderflow));
+    assert_eq!(e, before);
+    assert_eq!(ExecError::PcUnderflow.to_string(), "Program Counter Underflow");
+
+    e.execute_checked(Instruction::Jump(-4)).unwrap();
+    assert_eq!(e.pc, 0);
+
+    // the furthest jump forward is fine from the start of the program, but not from anywhere after it
+    e.execute_checked(Instruction::Jump(i64::MAX)).unwrap();
+    assert_eq!(e.pc, i64::MAX as usize);
+    let mut e = Emulator::new();
+    e.execute_checked(Instruction::NoOperation(0)).unwrap();
+    let before = e.clone();
+    assert_eq!(e.execute_checked(Instruction::Jump(i64::MAX)), Err(ExecError::PcOverflow));
+    assert_eq!(e, before);
+    assert_eq!(ExecError::PcOverflow.to_string(), "Program Counter Overflow");
+    // a program can only get there with a parsed instruction, and stepping it doesn't panic either
+    let instrs = [Instruction::NoOperation(0), Instruction::from_str("jmp +9223372036854775807").unwrap()];
+    let mut e = Emulator::new();
+    assert_eq!(e.step(&instrs), StepResult::Running);
+    assert_eq!(e.step(&instrs), StepResult::OutOfBounds);
+}
+
+#[test]
+#[should_panic(expected = "Program Counter Underflow")]
+fn test_execute_underflow_panics() {
+    Emulator::new().execute(Instruction::Jump(-1));
+}
+
+#[test]
+fn test_mutable_indices() {
+    let instrs = sample_program();
+    assert_eq!(mutable_indices(&instrs), vec![0, 2, 4, 7]);
+    assert!(mutable_indices(&instrs[5..7]).is_empty());
+}
+
+#[test]
+fn test_find_fix_linear() {
+    let instrs = sample_program();
+
+    let (idx, emulator) = find_fix_linear(&instrs).unwrap();
+    match run_program(Emulator::new(), &instrs, 1, HashSet::new()) {
+        TerminationCriteria::Terminated(e, mutated) => {
+            assert_eq!(Some(idx), mutated);
+            assert_eq!(emulator, e);
+        }
+        other => panic!("expected program to terminate, got {:?}", other),
+    }
+    assert_eq!(idx, 7);
+    assert_eq!(emulator.accumulator, 8);
+
+    assert!(find_fix_linear(&[Instruction::Accumulate(1), Instruction::Jump(-1), Instruction::Jump(-1)]).is_none());
+}
+
+#[test]
+fn test_run_until_loop() {
+    let instrs = sample_program();
+
+    let (pc, emulator) = run_until_loop(&instrs);
+    assert_eq!(pc, 1);
+    assert_eq!(emulator.accumulator, 5);
+}
+
+#[test]
+fn test_assemble() {
+    let source = [
+        "start:",
+        "    acc +1",
+        "    jmp check",
+        "loop: acc +3",
+        "    jmp start",
+        "check:",
+        "    jmp loop",
+        "    acc +6",
+    ].join("\n");
+    let relative: Vec<Instruction> = [
+        "acc +1", "jmp +3", "acc +3", "jmp -3", "jmp -2", "acc +6",
+    ].iter().map(|i| Instruction::from_str(i).unwrap()).collect();
+    assert_eq!(assemble(&source).unwrap(), relative);
+
+    let err = assemble("acc +1\njmp nowhere").unwrap_err();
+    assert!(err.to_string().contains("nowhere"));
+    assemble("a:\na: nop +0").unwrap_err();
+    assert_eq!(assemble("top: acc +1\njmp top").unwrap(), vec![Instruction::Accumulate(1), Instruction::Jump(-1)]);
+}
+
+#[test]
+fn test_run_program_bounded() {
+    // a tight loop over all three instructions
+    let instrs = [Instruction::Accumulate(1), Instruction::NoOperation(0), Instruction::Jump(-2)];
+    match run_program_bounded(Emulator::new(), &instrs, 2) {
+        TerminationCriteria::StepLimitExceeded(e) => {
+            assert_eq!(e.pc, 2);
+            assert_eq!(e.accumulator, 1);
+        }
+        other => panic!("expected the step limit to be hit, got {:?}", other),
+    }
+    match run_program_bounded(Emulator::new(), &instrs, 100) {
+        TerminationCriteria::InfiniteLoop(visited) => assert_eq!(visited.len(), 3),
+        other => panic!("expected an infinite loop, got {:?}", other),
+    }
+
+    let instrs = [Instruction::Accumulate(1), Instruction::Accumulate(2)];
+    match run_program_bounded(Emulator::new(), &instrs, 2) {
+        TerminationCriteria::Terminated(e, None) => assert_eq!(e.accumulator, 3),
+        other => panic!("expected program to terminate, got {:?}", other),
+    }
+
+    // jumping before the start of the program stops it rather than panicking
+    let instrs = [Instruction::Accumulate(1), Instruction::Jump(-5)];
+    match run_program_bounded(Emulator::new(), &instrs, 10) {
+        TerminationCriteria::OutOfBounds(e) => {
+            assert_eq!(e.pc, 1);
+            assert_eq!(e.accumulator, 1);
+        }
+        other => panic!("expected the program to go out of bounds, got {:?}", other),
+    }
+    // and so does jumping well past the end
+    match run_program_bounded(Emulator::new(), &[Instruction::Jump(5)], 10) {
+        TerminationCriteria::OutOfBounds(e) => assert_eq!(e.pc, 5),
+        other => panic!("expected the program to go out of bounds, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_emulator_accumulator_wraps() {
+    let mut e = Emulator::new();
+    e.execute(Instruction::Accumulate(i64::MAX));
+    e.execute(Instruction::Accumulate(1));
+    assert_eq!(e.accumulator, i64::MIN);
+    e.execute(Instruction::Accumulate(-1));
+    assert_eq!(e.accumulator, i64::MAX);
+
+    e.execute(Instruction::Multiply(2));
+    assert_eq!(e.accumulator, -2);
+}
+
+#[test]
+fn test_emulator_acc_log() {
+    let instrs = sample_program();
+
+    let mut e = Emulator::with_acc_log();
+    let mut seen: HashSet<usize> = HashSet::new();
+    while seen.insert(e.pc) {
+        e.execute(instrs[e.pc]);
+    }
+    assert_eq!(e.acc_log(), &[(1, 1), (6, 1), (3, 3)]);
+    assert_eq!(e.acc_log().iter().map(|(_, delta)| delta).sum::<i64>(), e.accumulator);
+
+    e.reset();
+    assert!(e.acc_log().is_empty());
+    assert_eq!(e, Emulator::with_acc_log());
+
+    // the log is off by default
+    let mut e = Emulator::new();
+    e.execute(instrs[1]);
+    assert!(e.acc_log().is_empty());
+    assert_eq!(e.acc_log, None);
+}
+
+#[test]
+fn test_emulator_trace() {
+    let instrs = sample_program();
+
+    let mut e = Emulator::with_trace();
+    let mut seen: HashSet<usize> = HashSet::new();
+    while seen.insert(e.pc) {
+        e.execute(instrs[e.pc]);
+    }
+    assert_eq!(e.trace, Some(vec![0, 1, 1, 2, 2, 5, 5]));
+
+    // tracing is off by default
+    let mut e = Emulator::new();
+    e.execute(instrs[1]);
+    assert_eq!(e.trace, None);
+}
+
+#[test]
+fn test_emulator_reset() {
+    let instrs: Vec<Instruction> = [
+        "acc +3", "out", "mul +4", "jmp +2", "acc -99", "out",
+    ].iter().map(|i| Instruction::from_str(i).unwrap()).collect();
+
+    let mut e = Emulator::new();
+    while e.step(&instrs) == StepResult::Running {}
+    assert_eq!(e.output, vec![3, 12]);
+    e.reset();
+    assert_eq!(e, Emulator::new());
+
+    let mut e = Emulator::with_trace();
+    while e.step(&instrs) == StepResult::Running {}
+    e.reset();
+    assert_eq!(e, Emulator::with_trace());
+}
+
+#[test]
+fn test_loop_length() {
+    let instrs = [Instruction::NoOperation(0), Instruction::Accumulate(1), Instruction::Jump(-2)];
+    assert_eq!(loop_length(&instrs), Some(3));
+
+    // the loop doesn't have to start at the first instruction
+    let instrs = sample_program();
+    assert_eq!(loop_length(&instrs), Some(6));
+
+    assert_eq!(loop_length(&[Instruction::Jump(0)]), Some(1));
+    assert_eq!(loop_length(&[Instruction::Accumulate(1), Instruction::NoOperation(0)]), None);
+    assert_eq!(loop_length(&[Instruction::Jump(5)]), None);
+    assert_eq!(loop_length(&[]), None);
 }
\ No newline at end of file
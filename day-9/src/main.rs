@@ -1,91 +1,402 @@
 use std::collections::{VecDeque, HashMap};
 use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, BufRead, BufReader};
 
-fn main() {
-    let input: Vec<i64> = include_str!("../input.txt").lines().map(|line| line.parse().unwrap()).collect();
-    let invalid_number = find_invalid_number(25, &input).unwrap();
+use clap::{App, Arg};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let matches = App::new("day-9")
+        .arg(Arg::with_name("input")
+             .required(true)
+             .takes_value(true)
+             .help("the input to the program"))
+        .arg(Arg::with_name("window")
+             .short("w")
+             .long("window")
+             .default_value("25")
+             .takes_value(true)
+             .help("number of previous values a new value may be a sum of"))
+        .get_matches();
+
+    let window_size: usize = matches.value_of("window").unwrap().parse()?;
+    let input = read_numbers(File::open(matches.value_of("input").unwrap())?)?;
+    let invalid_number = find_invalid_number(window_size, &input)?;
     println!("couldn't insert value {}", invalid_number);
-    let cypher_break = find_contiguous_sum(invalid_number, &input).unwrap();
+    let cypher_break = find_contiguous_sum(invalid_number, &input)?;
     println!("cypher weakness: {}", cypher_break);
+    Ok(())
+}
+
+/**
+ * Reads one number per line
+ */
+pub fn read_numbers(input: impl Read) -> Result<Vec<i64>, Box<dyn Error>> {
+    let mut output = Vec::new();
+    for line in BufReader::new(input).lines() {
+        let line: String = line?;
+        output.push(line.parse()?);
+    }
+    Ok(output)
 }
 
 pub fn find_invalid_number(window_size: usize, input: &[i64]) -> Result<i64, Box<dyn Error>> {
-    let mut window = CipherWindow::new(window_size);
-    for value in input.iter() {
-        match window.insert(*value) {
-            Ok(_) => (),
-            Err(_) => {
-                return Ok(*value);
-            }
+    let idx = find_invalid_index(window_size, input)?;
+    Ok(input[idx])
+}
+
+/**
+ * Returns the index of the first value in `input` that isn't a sum of two of the `window_size` values before it
+ */
+pub fn find_invalid_index(window_size: usize, input: &[i64]) -> Result<usize, Box<dyn Error>> {
+    let mut window = CipherWindow::new(window_size, false);
+    for (idx, value) in input.iter().enumerate() {
+        if window.insert(*value) == InsertOutcome::Rejected {
+            return Ok(idx);
         }
     }
-    Err(format!("All numbers were valid").into())
+    Err("All numbers were valid".into())
+}
+
+/**
+ * Returns the index and value of every number in `input` that isn't a sum of two of the `window_size` values before
+ * it. Invalid numbers still move the window along, so each number is checked against the ones right before it.
+ */
+pub fn find_all_invalid(window_size: usize, input: &[i64]) -> Vec<(usize, i64)> {
+    let mut window = CipherWindow::new(window_size, false);
+    let mut invalid = Vec::new();
+    for (idx, value) in input.iter().enumerate() {
+        if window.insert(*value) == InsertOutcome::Rejected {
+            invalid.push((idx, *value));
+            window.push(*value);
+            window.pop();
+        }
+    }
+    invalid
 }
 
 pub fn find_contiguous_sum(value_to_find: i64, input: &[i64]) -> Result<i64, Box<dyn Error>> {
-    for winsize in 2..input.len() {
-        let found = input.windows(winsize)
-            .filter(|window| (*window).iter().sum::<i64>() == value_to_find)
-            .next();
-        
-        match found {
-            Some(n) => {
-                let mut v = Vec::from(n);
-                v.sort();
-                return Ok(v.first().unwrap() + v.last().unwrap());
-            }
-            None => (),
+    let (start, end) = match find_contiguous_range(value_to_find, input) {
+        Some(range) => range,
+        None => return Err("couldn't find it".into()),
+    };
+    let window = &input[start..=end];
+    Ok(window.iter().min().unwrap() + window.iter().max().unwrap())
+}
+
+/**
+ * Finds a run of at least two contiguous elements of `input` that sum up to `value_to_find`.
+ * Returns the inclusive start and end indices of the run.
+ *
+ * This slides a window along the input, growing it on the right while the sum is too small and shrinking it from
+ * the left while it's too big. That only works if every element of `input` is non-negative.
+ */
+pub fn find_contiguous_range(value_to_find: i64, input: &[i64]) -> Option<(usize, usize)> {
+    let mut start = 0;
+    let mut sum = 0;
+    for (end, value) in input.iter().enumerate() {
+        sum += value;
+        while sum > value_to_find && start < end {
+            sum -= input[start];
+            start += 1;
+        }
+        if sum == value_to_find && start < end {
+            return Some((start, end));
+        }
+    }
+    None
+}
+
+/**
+ * Finds `k` elements of `input`, not necessarily next to each other, that sum up to `value`.
+ * Elements are returned in the order they appear in `input`.
+ */
+pub fn find_subset_sum(value: i64, k: usize, input: &[i64]) -> Option<Vec<i64>> {
+    if k == 0 {
+        return if value == 0 { Some(Vec::new()) } else { None };
+    }
+    // pick each element in turn as the first of the subset, and look for the rest after it
+    for (idx, first) in input.iter().enumerate() {
+        if let Some(mut rest) = find_subset_sum(value - first, k - 1, &input[idx + 1..]) {
+            rest.insert(0, *first);
+            return Some(rest);
         }
     }
-    Err(format!("couldn't find it").into())
+    None
 }
 
 pub struct CipherWindow {
     window: VecDeque<i64>,
     window_size: usize,
+    /// whether a value can be the sum of a single window entry with itself
+    allow_same: bool,
+    /// how many times each value appears in `window`. Kept in step with the window as values come and go.
+    counts: HashMap<i64, usize>,
 }
 
+/// What happened to a value given to `CipherWindow::insert`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InsertOutcome {
+    /// the window wasn't full yet, so the value was taken without checking it
+    Filling,
+    /// the value was a sum of two values in the window, and has been added to it
+    Accepted,
+    /// the value wasn't a sum of two values in the window. The window is unchanged.
+    Rejected,
+}
+
+impl InsertOutcome {
+    /// Treats a rejected value as an error, and anything else as success
+    pub fn into_result(self) -> Result<(), InsertError> {
+        match self {
+            Self::Filling | Self::Accepted => Ok(()),
+            Self::Rejected => Err(InsertError::NotASum),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum InsertError {
     NotASum,
 }
 
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotASum => write!(f, "value is not a sum of two distinct window entries"),
+        }
+    }
+}
+
+impl Error for InsertError {}
+
 impl CipherWindow {
-    pub fn new(window_size: usize) -> Self {
+    /// Construct an empty window. If `allow_same` is set, a value that's double one entry in the window is a valid sum
+    /// even if that entry only appears once.
+    pub fn new(window_size: usize, allow_same: bool) -> Self {
         Self {
             window: VecDeque::new(),
-            window_size
+            window_size,
+            allow_same,
+            counts: HashMap::new(),
         }
     }
-    pub fn insert(&mut self, value: i64) -> Result<(), InsertError> {
+    /// Construct a window already filled with `preamble`, which must have exactly `window_size` values in it.
+    /// Same values can't be paired with themselves.
+    pub fn with_preamble(window_size: usize, preamble: &[i64]) -> Result<Self, Box<dyn Error>> {
+        if preamble.len() != window_size {
+            return Err(format!("preamble has {} values, but the window holds {}", preamble.len(), window_size).into());
+        }
+        let mut window = Self::new(window_size, false);
+        for value in preamble {
+            window.push(*value);
+        }
+        Ok(window)
+    }
+    pub fn insert(&mut self, value: i64) -> InsertOutcome {
         if self.window.len() < self.window_size {
-            self.window.push_back(value);
-            return Ok(());
+            self.push(value);
+            return InsertOutcome::Filling;
         }
-        let set = self.window.iter()
-            .fold(HashMap::new(), |mut m, elem| {
-                let e = m.entry(*elem).or_insert(0);
-                *e += 1;
-                m
-            });
-        let mut found = false;
-        for each_value in self.window.iter() {
+        let found = self.window.iter().any(|each_value| {
             let supposed_second_value = value - *each_value;
-            let is_match = match set.get(&supposed_second_value) {
-                Some(v) if value == *each_value => *v > 1,
+            match self.counts.get(&supposed_second_value) {
+                // both halves are the same value, so it has to be in the window twice unless that's allowed
+                Some(n) if supposed_second_value == *each_value => self.allow_same || *n > 1,
                 Some(_) => true,
                 None => false,
-            };
-            if is_match {
-                found = true;
-                break;
             }
-        }
+        });
         if found {
-            self.window.push_back(value);
-            self.window.pop_front();
-            Ok(())
+            self.push(value);
+            self.pop();
+            InsertOutcome::Accepted
         } else {
-            Err(InsertError::NotASum)
+            InsertOutcome::Rejected
         }
     }
+    /// The values currently in the window, oldest first
+    pub fn contents(&self) -> &VecDeque<i64> {
+        &self.window
+    }
+    /// Returns true once the window holds `window_size` values, and new values must be sums
+    pub fn is_full(&self) -> bool {
+        self.window.len() >= self.window_size
+    }
+    fn push(&mut self, value: i64) {
+        self.window.push_back(value);
+        *self.counts.entry(value).or_insert(0) += 1;
+    }
+    fn pop(&mut self) {
+        if let Some(value) = self.window.pop_front() {
+            if let Some(n) = self.counts.get_mut(&value) {
+                *n -= 1;
+                if *n == 0 {
+                    self.counts.remove(&value);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_insert_error_display() {
+    assert_eq!(InsertError::NotASum.to_string(), "value is not a sum of two distinct window entries");
+
+    let mut window = CipherWindow::new(2, false);
+    window.insert(1);
+    window.insert(2);
+    let err: Box<dyn Error> = window.insert(5).into_result().unwrap_err().into();
+    assert_eq!(err.to_string(), "value is not a sum of two distinct window entries");
+}
+
+#[test]
+fn test_find_subset_sum() {
+    let input = [1, 10, 2, 20, 4];
+    assert_eq!(find_subset_sum(7, 3, &input), Some(vec![1, 2, 4]));
+    assert!(find_contiguous_sum(7, &input).is_err());
+
+    assert_eq!(find_subset_sum(30, 2, &input), Some(vec![10, 20]));
+    assert_eq!(find_subset_sum(100, 3, &input), None);
+    assert_eq!(find_subset_sum(7, 6, &input), None);
+}
+
+#[test]
+fn test_find_contiguous_range() {
+    let input = [35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576];
+    assert_eq!(find_contiguous_range(127, &input), Some((2, 5)));
+    assert_eq!(find_contiguous_sum(127, &input).unwrap(), 62);
+    assert_eq!(find_contiguous_range(1, &input), None);
+}
+
+#[test]
+fn test_find_contiguous_range_matches_brute_force() {
+    // the original search: try every window size, smallest first
+    fn brute_force(value_to_find: i64, input: &[i64]) -> Option<(usize, usize)> {
+        (2..input.len()).find_map(|winsize| {
+            input.windows(winsize)
+                .position(|window| window.iter().sum::<i64>() == value_to_find)
+                .map(|start| (start, start + winsize - 1))
+        })
+    }
+    // some not-very-random numbers
+    let mut seed: i64 = 12345;
+    let input: Vec<i64> = (0..200).map(|_| {
+        seed = (seed * 1103515245 + 12345) % 2147483648;
+        seed % 1000
+    }).collect();
+
+    for (start, end) in [(3, 9), (10, 40), (120, 180)].iter() {
+        let target: i64 = input[*start..=*end].iter().sum();
+        assert_eq!(find_contiguous_range(target, &input), brute_force(target, &input));
+        assert_eq!(find_contiguous_range(target, &input), Some((*start, *end)));
+    }
+}
+
+#[test]
+fn test_find_invalid_number() {
+    let input = [35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576];
+    assert_eq!(find_invalid_number(5, &input).unwrap(), 127);
+    assert_eq!(find_invalid_index(5, &input).unwrap(), 14);
+    find_invalid_index(5, &input[..14]).unwrap_err();
+
+    // a value can't be paired with itself unless it's in the window twice
+    let mut window = CipherWindow::new(3, false);
+    for value in [1, 2, 5].iter() {
+        assert_eq!(window.insert(*value), InsertOutcome::Filling);
+    }
+    assert_eq!(window.insert(10), InsertOutcome::Rejected);
+    assert_eq!(window.insert(7), InsertOutcome::Accepted);
+    assert_eq!(window.insert(14), InsertOutcome::Rejected);
+    assert_eq!(window.insert(12), InsertOutcome::Accepted);
+}
+
+#[test]
+fn test_find_all_invalid() {
+    let mut input = vec![35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576];
+    assert_eq!(find_all_invalid(5, &input), vec![(14, 127)]);
+
+    // 885 = 309 + 576 is fine, 10 isn't
+    input.extend_from_slice(&[885, 10]);
+    assert_eq!(find_all_invalid(5, &input), vec![(14, 127), (21, 10)]);
+    assert_eq!(find_all_invalid(5, &input[..14]), vec![]);
+}
+
+#[test]
+fn test_read_numbers() {
+    use std::io::Cursor;
+    let input = read_numbers(Cursor::new("35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n")).unwrap();
+    assert_eq!(input.len(), 15);
+    assert_eq!(find_invalid_number(5, &input).unwrap(), 127);
+
+    read_numbers(Cursor::new("35\nabc\n")).unwrap_err();
+}
+
+#[test]
+fn test_part_1() {
+    use std::io::Cursor;
+    let input = read_numbers(Cursor::new(include_str!("../input.txt"))).unwrap();
+    assert_eq!(find_invalid_number(25, &input).unwrap(), 138879426);
+    assert_eq!(find_contiguous_sum(138879426, &input).unwrap(), 23761694);
+}
+
+#[test]
+fn test_window_contents() {
+    let mut window = CipherWindow::new(3, false);
+    assert!(window.contents().is_empty());
+    assert!(!window.is_full());
+
+    window.insert(1);
+    window.insert(2);
+    assert_eq!(window.contents(), &VecDeque::from(vec![1, 2]));
+    assert!(!window.is_full());
+
+    window.insert(5);
+    assert!(window.is_full());
+    window.insert(7);
+    assert_eq!(window.contents(), &VecDeque::from(vec![2, 5, 7]));
+    assert_eq!(window.insert(100), InsertOutcome::Rejected);
+    assert_eq!(window.contents(), &VecDeque::from(vec![2, 5, 7]));
+}
+
+#[test]
+fn test_insert_outcome() {
+    let mut window = CipherWindow::new(2, false);
+    assert_eq!(window.insert(1), InsertOutcome::Filling);
+    assert_eq!(window.insert(2), InsertOutcome::Filling);
+    assert_eq!(window.insert(3), InsertOutcome::Accepted);
+    assert_eq!(window.insert(3), InsertOutcome::Rejected);
+    assert_eq!(window.insert(5), InsertOutcome::Accepted);
+
+    assert_eq!(InsertOutcome::Filling.into_result(), Ok(()));
+    assert_eq!(InsertOutcome::Accepted.into_result(), Ok(()));
+    assert_eq!(InsertOutcome::Rejected.into_result(), Err(InsertError::NotASum));
+}
+
+#[test]
+fn test_window_allow_same() {
+    for allow_same in [false, true].iter() {
+        let mut window = CipherWindow::new(3, *allow_same);
+        for value in [1, 2, 5].iter() {
+            window.insert(*value);
+        }
+        // 10 is only 5 + 5
+        let expected = if *allow_same { InsertOutcome::Accepted } else { InsertOutcome::Rejected };
+        assert_eq!(window.insert(10), expected);
+        // sums of distinct entries are fine either way
+        assert_eq!(window.insert(7), InsertOutcome::Accepted);
+    }
+}
+
+#[test]
+fn test_window_with_preamble() {
+    let mut window = CipherWindow::with_preamble(5, &[35, 20, 15, 25, 47]).unwrap();
+    assert!(window.is_full());
+    assert_eq!(window.contents(), &VecDeque::from(vec![35, 20, 15, 25, 47]));
+    assert_eq!(window.insert(40), InsertOutcome::Accepted);
+    assert_eq!(window.insert(1000), InsertOutcome::Rejected);
+
+    assert!(CipherWindow::with_preamble(5, &[35, 20, 15, 25]).is_err());
+    assert!(CipherWindow::with_preamble(5, &[35, 20, 15, 25, 47, 40]).is_err());
 }
\ No newline at end of file